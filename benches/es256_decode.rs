@@ -0,0 +1,31 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use jwt_rustcrypto::{
+    decode, encode, Algorithm, Header, SigningKey, ValidationOptions, VerifyingKey,
+};
+use serde_json::json;
+use std::fs;
+use std::hint::black_box;
+
+fn bench_es256_decode(c: &mut Criterion) {
+    let private_key = fs::read("tests/keys/ec_private_key_p256_pkcs8.pem").unwrap();
+    let public_key = fs::read("tests/keys/ec_public_key_p256_pkcs8.pem").unwrap();
+    let signing_key = SigningKey::from_ec_pem(&private_key).unwrap();
+    let payload = json!({ "sub": "1234567890", "name": "John Doe", "iat": 1516239022 });
+    let token = encode(&Header::new(Algorithm::ES256), &signing_key, &payload).unwrap();
+    let validation_options = ValidationOptions::new(Algorithm::ES256).without_expiry();
+
+    // The key is parsed once and reused, as on a hot verification path.
+    let verifying_key = VerifyingKey::from_ec_pem(&public_key).unwrap();
+    c.bench_function("decode es256 reused key", |b| {
+        b.iter(|| decode(black_box(&token), &verifying_key, &validation_options))
+    });
+    c.bench_function("decode es256 key parsed per token", |b| {
+        b.iter(|| {
+            let verifying_key = VerifyingKey::from_ec_pem(black_box(&public_key)).unwrap();
+            decode(black_box(&token), &verifying_key, &validation_options)
+        })
+    });
+}
+
+criterion_group!(benches, bench_es256_decode);
+criterion_main!(benches);
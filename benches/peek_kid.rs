@@ -0,0 +1,22 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use jwt_rustcrypto::{decode_only, encode, peek_kid, Algorithm, Header, SigningKey};
+use serde_json::json;
+use std::hint::black_box;
+
+fn bench_peek_kid(c: &mut Criterion) {
+    let header = Header {
+        kid: Some("key-1".to_string()),
+        x5t: Some("dGh1bWJwcmludA".to_string()),
+        ..Header::new(Algorithm::HS256)
+    };
+    let payload = json!({ "sub": "1234567890", "name": "John Doe", "iat": 1516239022 });
+    let token = encode(&header, &SigningKey::from_secret(b"mysecret"), &payload).unwrap();
+
+    c.bench_function("peek_kid", |b| b.iter(|| peek_kid(black_box(&token))));
+    c.bench_function("decode_only header kid", |b| {
+        b.iter(|| decode_only(black_box(&token)).map(|decoded| decoded.header.kid))
+    });
+}
+
+criterion_group!(benches, bench_peek_kid);
+criterion_main!(benches);
@@ -0,0 +1,83 @@
+//! Generates a key, signs a token with custom claims and verifies it again,
+//! for RS256 and EdDSA.
+//!
+//! Run with `cargo run --example roundtrip`.
+use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ed25519_dalek::pkcs8::{EncodePrivateKey, EncodePublicKey};
+use jwt_rustcrypto::{
+    decode, encode, Algorithm, Header, SigningKey, ValidationOptions, VerifyingKey,
+};
+use rsa::pkcs8::LineEnding;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    role: String,
+    exp: u64,
+}
+
+fn generate_rsa_keys() -> Result<(SigningKey, VerifyingKey), Box<dyn Error>> {
+    let private_key = rsa::RsaPrivateKey::new(&mut rand::thread_rng(), 2048)?;
+    let private_pem = rsa::pkcs8::EncodePrivateKey::to_pkcs8_pem(&private_key, LineEnding::LF)?;
+    let public_pem = rsa::pkcs8::EncodePublicKey::to_public_key_pem(
+        &private_key.to_public_key(),
+        LineEnding::LF,
+    )?;
+
+    Ok((
+        SigningKey::from_rsa_pem(private_pem.as_bytes())?,
+        VerifyingKey::from_rsa_pem(public_pem.as_bytes())?,
+    ))
+}
+
+fn generate_ed25519_keys() -> Result<(SigningKey, VerifyingKey), Box<dyn Error>> {
+    let private_key = ed25519_dalek::SigningKey::from_bytes(&rand::random());
+    let private_pem = private_key.to_pkcs8_pem(LineEnding::LF)?;
+    let public_pem = private_key
+        .verifying_key()
+        .to_public_key_pem(LineEnding::LF)?;
+
+    Ok((
+        SigningKey::from_ed_pem(private_pem.as_bytes())?,
+        VerifyingKey::from_ed_pem(public_pem.as_bytes())?,
+    ))
+}
+
+fn roundtrip(
+    alg: Algorithm,
+    signing_key: &SigningKey,
+    verifying_key: &VerifyingKey,
+) -> Result<(), Box<dyn Error>> {
+    let claims = Claims {
+        sub: "1234567890".to_string(),
+        role: "admin".to_string(),
+        exp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 3600,
+    };
+
+    let token = encode(&Header::new(alg), signing_key, &claims)?;
+    println!("[{}] signed token: {}", alg, token);
+
+    let decoded = decode(&token, verifying_key, &ValidationOptions::new(alg))?;
+    let decoded_claims: Claims = decoded.deserialize_claims()?;
+    println!("[{}] verified claims: {:?}", alg, decoded_claims);
+
+    if decoded_claims != claims {
+        return Err(format!("[{}] claims changed in the round trip", alg).into());
+    }
+    Ok(())
+}
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    println!("[RS256] generating a 2048-bit RSA key");
+    let (signing_key, verifying_key) = generate_rsa_keys()?;
+    roundtrip(Algorithm::RS256, &signing_key, &verifying_key)?;
+
+    println!("[EdDSA] generating an Ed25519 key");
+    let (signing_key, verifying_key) = generate_ed25519_keys()?;
+    roundtrip(Algorithm::EdDSA, &signing_key, &verifying_key)?;
+
+    Ok(())
+}
@@ -1,7 +1,14 @@
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
+use crate::Error;
+
+/// Signing algorithms supported by the crate.
+///
+/// New algorithms may be added in minor releases, so matches outside the
+/// crate need a wildcard arm.
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum Algorithm {
     HS256,
     HS384,
@@ -16,6 +23,97 @@ pub enum Algorithm {
     ES256K,
     ES384,
     ES512,
+    EdDSA,
+}
+
+impl Algorithm {
+    /// The kind of key the algorithm signs and verifies with.
+    pub(crate) fn key_kind(&self) -> &'static str {
+        match self {
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => "secret",
+            Algorithm::RS256
+            | Algorithm::RS384
+            | Algorithm::RS512
+            | Algorithm::PS256
+            | Algorithm::PS384
+            | Algorithm::PS512 => "RSA",
+            Algorithm::ES256 | Algorithm::ES256K | Algorithm::ES384 | Algorithm::ES512 => "EC",
+            Algorithm::EdDSA => "Ed25519",
+        }
+    }
+
+    /// Length in bytes of the digest the algorithm signs. EdDSA uses the
+    /// SHA-512 digest of Ed25519.
+    pub fn hash_len(&self) -> usize {
+        match self {
+            Algorithm::HS256
+            | Algorithm::RS256
+            | Algorithm::PS256
+            | Algorithm::ES256
+            | Algorithm::ES256K => 32,
+            Algorithm::HS384 | Algorithm::RS384 | Algorithm::PS384 | Algorithm::ES384 => 48,
+            Algorithm::HS512
+            | Algorithm::RS512
+            | Algorithm::PS512
+            | Algorithm::ES512
+            | Algorithm::EdDSA => 64,
+        }
+    }
+
+    /// Length in bytes of the signatures of fixed-width algorithms, with
+    /// ECDSA signatures in their `r || s` form. `None` for RSA, whose
+    /// signatures are as long as the modulus.
+    pub fn signature_len(&self) -> Option<usize> {
+        match self {
+            Algorithm::HS256 => Some(32),
+            Algorithm::HS384 => Some(48),
+            Algorithm::HS512 | Algorithm::EdDSA => Some(64),
+            Algorithm::ES256 | Algorithm::ES256K => Some(64),
+            Algorithm::ES384 => Some(96),
+            Algorithm::ES512 => Some(132),
+            Algorithm::RS256
+            | Algorithm::RS384
+            | Algorithm::RS512
+            | Algorithm::PS256
+            | Algorithm::PS384
+            | Algorithm::PS512 => None,
+        }
+    }
+
+    /// Parses an algorithm name ignoring ASCII case, e.g. `rs256`.
+    pub(crate) fn from_str_ignore_ascii_case(s: &str) -> Option<Self> {
+        [
+            Algorithm::HS256,
+            Algorithm::HS384,
+            Algorithm::HS512,
+            Algorithm::RS256,
+            Algorithm::RS384,
+            Algorithm::RS512,
+            Algorithm::PS256,
+            Algorithm::PS384,
+            Algorithm::PS512,
+            Algorithm::ES256,
+            Algorithm::ES256K,
+            Algorithm::ES384,
+            Algorithm::ES512,
+            Algorithm::EdDSA,
+        ]
+        .into_iter()
+        .find(|alg| alg.to_string().eq_ignore_ascii_case(s))
+    }
+
+    /// Fails with `Error::KeyAlgorithmMismatch` unless a key of `key_kind`
+    /// can be used with the algorithm.
+    pub(crate) fn check_key_kind(&self, key_kind: &'static str) -> Result<(), Error> {
+        if self.key_kind() == key_kind {
+            Ok(())
+        } else {
+            Err(Error::KeyAlgorithmMismatch {
+                alg: *self,
+                key_kind: key_kind.to_string(),
+            })
+        }
+    }
 }
 
 impl std::fmt::Display for Algorithm {
@@ -34,6 +132,7 @@ impl std::fmt::Display for Algorithm {
             Algorithm::ES256K => "ES256K",
             Algorithm::ES384 => "ES384",
             Algorithm::ES512 => "ES512",
+            Algorithm::EdDSA => "EdDSA",
         };
         write!(f, "{}", alg_str)
     }
@@ -50,10 +149,64 @@ impl FromStr for Algorithm {
             "RS256" => Ok(Algorithm::RS256),
             "RS384" => Ok(Algorithm::RS384),
             "RS512" => Ok(Algorithm::RS512),
+            "PS256" => Ok(Algorithm::PS256),
+            "PS384" => Ok(Algorithm::PS384),
+            "PS512" => Ok(Algorithm::PS512),
             "ES256" => Ok(Algorithm::ES256),
+            "ES256K" => Ok(Algorithm::ES256K),
             "ES384" => Ok(Algorithm::ES384),
             "ES512" => Ok(Algorithm::ES512),
+            "EdDSA" => Ok(Algorithm::EdDSA),
             _ => Err(format!("Unsupported algorithm: {}", s)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_len() {
+        for (alg, hash_len) in [
+            (Algorithm::HS256, 32),
+            (Algorithm::HS384, 48),
+            (Algorithm::HS512, 64),
+            (Algorithm::RS256, 32),
+            (Algorithm::RS384, 48),
+            (Algorithm::RS512, 64),
+            (Algorithm::PS256, 32),
+            (Algorithm::PS384, 48),
+            (Algorithm::PS512, 64),
+            (Algorithm::ES256, 32),
+            (Algorithm::ES256K, 32),
+            (Algorithm::ES384, 48),
+            (Algorithm::ES512, 64),
+            (Algorithm::EdDSA, 64),
+        ] {
+            assert_eq!(alg.hash_len(), hash_len, "{}", alg);
+        }
+    }
+
+    #[test]
+    fn test_signature_len() {
+        for (alg, signature_len) in [
+            (Algorithm::HS256, Some(32)),
+            (Algorithm::HS384, Some(48)),
+            (Algorithm::HS512, Some(64)),
+            (Algorithm::RS256, None),
+            (Algorithm::RS384, None),
+            (Algorithm::RS512, None),
+            (Algorithm::PS256, None),
+            (Algorithm::PS384, None),
+            (Algorithm::PS512, None),
+            (Algorithm::ES256, Some(64)),
+            (Algorithm::ES256K, Some(64)),
+            (Algorithm::ES384, Some(96)),
+            (Algorithm::ES512, Some(132)),
+            (Algorithm::EdDSA, Some(64)),
+        ] {
+            assert_eq!(alg.signature_len(), signature_len, "{}", alg);
+        }
+    }
+}
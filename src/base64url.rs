@@ -0,0 +1,53 @@
+use base64::Engine;
+
+use crate::Error;
+
+/// Encodes `data` as unpadded base64url, the encoding used for every JWT
+/// segment and JWK member.
+pub fn base64url_encode(data: &[u8]) -> String {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(data)
+}
+
+/// Decodes unpadded base64url `data`.
+pub fn base64url_decode(data: &str) -> Result<Vec<u8>, Error> {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(data)
+        .map_err(Error::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VECTORS: [(&[u8], &str); 6] = [
+        (b"", ""),
+        (b"f", "Zg"),
+        (b"fo", "Zm8"),
+        (b"foo", "Zm9v"),
+        (b"foobar", "Zm9vYmFy"),
+        (&[0xfb, 0xff, 0xbf], "-_-_"),
+    ];
+
+    #[test]
+    fn test_base64url_encode() {
+        for (data, encoded) in VECTORS {
+            assert_eq!(base64url_encode(data), encoded);
+        }
+    }
+
+    #[test]
+    fn test_base64url_decode() {
+        for (data, encoded) in VECTORS {
+            assert_eq!(base64url_decode(encoded).unwrap(), data);
+        }
+
+        assert!(matches!(
+            base64url_decode("Zg=="),
+            Err(Error::Base64EncodeDecodeError(_))
+        ));
+        assert!(matches!(
+            base64url_decode("+/+/"),
+            Err(Error::Base64EncodeDecodeError(_))
+        ));
+    }
+}
@@ -0,0 +1,43 @@
+/// Returns the token of an `Authorization` header value using the `Bearer`
+/// scheme (RFC 6750), e.g. `Bearer eyJhbGciOi...`.
+///
+/// The scheme is matched case-insensitively and surrounding whitespace is
+/// trimmed. Returns `None` for other schemes or when the token is missing.
+pub fn extract_bearer(header_value: &str) -> Option<&str> {
+    let (scheme, token) = header_value.trim().split_once(char::is_whitespace)?;
+    if !scheme.eq_ignore_ascii_case("Bearer") {
+        return None;
+    }
+
+    Some(token.trim()).filter(|token| !token.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_bearer() {
+        assert_eq!(extract_bearer("Bearer abc.def.ghi"), Some("abc.def.ghi"));
+        assert_eq!(extract_bearer("bearer abc.def.ghi"), Some("abc.def.ghi"));
+        assert_eq!(extract_bearer("BEARER abc.def.ghi"), Some("abc.def.ghi"));
+        assert_eq!(
+            extract_bearer("  Bearer   abc.def.ghi \t"),
+            Some("abc.def.ghi")
+        );
+    }
+
+    #[test]
+    fn test_extract_bearer_wrong_scheme() {
+        assert_eq!(extract_bearer("Basic dXNlcjpwYXNz"), None);
+        assert_eq!(extract_bearer("Bearerabc.def.ghi"), None);
+        assert_eq!(extract_bearer("abc.def.ghi"), None);
+    }
+
+    #[test]
+    fn test_extract_bearer_missing_token() {
+        assert_eq!(extract_bearer("Bearer"), None);
+        assert_eq!(extract_bearer("Bearer   "), None);
+        assert_eq!(extract_bearer(""), None);
+    }
+}
@@ -0,0 +1,229 @@
+//! Compatibility layer mirroring the public API of the `jsonwebtoken` crate.
+//!
+//! Migrating code should mostly only need to swap `use jsonwebtoken::...` for
+//! `use jwt_rustcrypto::compat::...`. The known differences are:
+//!
+//! * Errors are reported with this crate's [`Error`] enum rather than
+//!   `jsonwebtoken::errors::Error`/`ErrorKind`.
+//! * `EncodingKey::from_*_der` and `DecodingKey::from_*_der` are not provided.
+//! * `Validation::reject_tokens_expiring_in_less_than` and
+//!   `insecure_disable_signature_validation` are not supported.
+//! * When `Validation::aud` is `None`, tokens carrying an `aud` claim are
+//!   accepted instead of being rejected.
+use std::collections::HashSet;
+
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{Algorithm, Error, ExpValidation, SigningKey, ValidationOptions, VerifyingKey};
+
+pub use crate::Header;
+
+/// A key used to sign tokens, equivalent to `jsonwebtoken::EncodingKey`.
+#[derive(Debug, Clone)]
+pub struct EncodingKey(SigningKey);
+
+impl EncodingKey {
+    pub fn from_secret(secret: &[u8]) -> Self {
+        Self(SigningKey::from_secret(secret))
+    }
+
+    pub fn from_base64_secret(secret: &str) -> Result<Self, Error> {
+        SigningKey::from_base64_secret(secret).map(Self)
+    }
+
+    pub fn from_rsa_pem(key: &[u8]) -> Result<Self, Error> {
+        SigningKey::from_rsa_pem(key).map(Self)
+    }
+
+    pub fn from_ec_pem(key: &[u8]) -> Result<Self, Error> {
+        SigningKey::from_ec_pem(key).map(Self)
+    }
+
+    pub fn from_ed_pem(key: &[u8]) -> Result<Self, Error> {
+        SigningKey::from_ed_pem(key).map(Self)
+    }
+
+    pub fn from_ed25519_seed(seed: &[u8; 32]) -> Result<Self, Error> {
+        SigningKey::from_ed25519_seed(seed).map(Self)
+    }
+}
+
+/// A key used to verify tokens, equivalent to `jsonwebtoken::DecodingKey`.
+#[derive(Debug, Clone)]
+pub struct DecodingKey(VerifyingKey);
+
+impl DecodingKey {
+    pub fn from_secret(secret: &[u8]) -> Self {
+        Self(VerifyingKey::from_secret(secret))
+    }
+
+    pub fn from_base64_secret(secret: &str) -> Result<Self, Error> {
+        VerifyingKey::from_base64_secret(secret).map(Self)
+    }
+
+    /// Builds an RSA key from the base64url encoded modulus and exponent, as
+    /// found in a JWK.
+    pub fn from_rsa_components(modulus: &str, exponent: &str) -> Result<Self, Error> {
+        let n = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(modulus)?;
+        let e = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(exponent)?;
+        VerifyingKey::from_rsa_components(&n, &e).map(Self)
+    }
+
+    pub fn from_rsa_pem(key: &[u8]) -> Result<Self, Error> {
+        VerifyingKey::from_rsa_pem(key).map(Self)
+    }
+
+    pub fn from_ec_pem(key: &[u8]) -> Result<Self, Error> {
+        VerifyingKey::from_ec_pem(key).map(Self)
+    }
+
+    pub fn from_ed_pem(key: &[u8]) -> Result<Self, Error> {
+        VerifyingKey::from_ed_pem(key).map(Self)
+    }
+
+    pub fn from_ed25519_public(public_key: &[u8; 32]) -> Result<Self, Error> {
+        VerifyingKey::from_ed25519_public(public_key).map(Self)
+    }
+}
+
+/// Validation settings, equivalent to `jsonwebtoken::Validation`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validation {
+    /// Claims that must be present in the token.
+    pub required_spec_claims: HashSet<String>,
+    /// Leeway (in seconds) applied to `exp` and `nbf`.
+    pub leeway: u64,
+    /// Validate the expiration time (`exp` claim).
+    pub validate_exp: bool,
+    /// Validate the not-before time (`nbf` claim).
+    pub validate_nbf: bool,
+    /// Validate the audience (`aud` claim) against `aud`.
+    pub validate_aud: bool,
+    /// Set of acceptable audience members.
+    pub aud: Option<HashSet<String>>,
+    /// Set of acceptable issuers.
+    pub iss: Option<HashSet<String>>,
+    /// Expected subject.
+    pub sub: Option<String>,
+    /// Allowed signing algorithms.
+    pub algorithms: Vec<Algorithm>,
+}
+
+impl Validation {
+    pub fn new(alg: Algorithm) -> Self {
+        Self {
+            required_spec_claims: HashSet::from(["exp".to_string()]),
+            leeway: 60,
+            validate_exp: true,
+            validate_nbf: false,
+            validate_aud: true,
+            aud: None,
+            iss: None,
+            sub: None,
+            algorithms: vec![alg],
+        }
+    }
+
+    pub fn set_audience<T: ToString>(&mut self, items: &[T]) {
+        self.aud = Some(items.iter().map(ToString::to_string).collect());
+    }
+
+    pub fn set_issuer<T: ToString>(&mut self, items: &[T]) {
+        self.iss = Some(items.iter().map(ToString::to_string).collect());
+    }
+
+    pub fn set_required_spec_claims<T: ToString>(&mut self, items: &[T]) {
+        self.required_spec_claims = items.iter().map(ToString::to_string).collect();
+    }
+
+    fn as_validation_options(&self) -> ValidationOptions {
+        // `jsonwebtoken` reports a missing `exp` through `required_spec_claims`,
+        // so the value is only checked when the claim is present.
+        ValidationOptions {
+            leeway: self.leeway,
+            exp_validation: if self.validate_exp {
+                ExpValidation::OptionalIfPresent
+            } else {
+                ExpValidation::Ignore
+            },
+            validate_nbf: self.validate_nbf,
+            audiences: self.aud.clone(),
+            validate_aud: self.validate_aud,
+            issuer: None,
+            subject: self.sub.clone(),
+            algorithms: self.algorithms.iter().copied().collect(),
+            required_claims: Some(self.required_spec_claims.clone()).filter(|c| !c.is_empty()),
+            ..ValidationOptions::default()
+        }
+    }
+}
+
+impl Default for Validation {
+    fn default() -> Self {
+        Self::new(Algorithm::HS256)
+    }
+}
+
+/// The decoded header and claims of a token, equivalent to
+/// `jsonwebtoken::TokenData`.
+#[derive(Debug, Clone)]
+pub struct TokenData<T> {
+    pub header: Header,
+    pub claims: T,
+}
+
+impl<T> TokenData<T> {
+    /// The decoded header.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// The decoded claims.
+    pub fn claims(&self) -> &T {
+        &self.claims
+    }
+
+    /// Consumes the token data, returning the claims.
+    pub fn into_claims(self) -> T {
+        self.claims
+    }
+}
+
+/// Encodes and signs `claims`, equivalent to `jsonwebtoken::encode`.
+pub fn encode<T: Serialize>(
+    header: &Header,
+    claims: &T,
+    key: &EncodingKey,
+) -> Result<String, Error> {
+    crate::encode(header, &key.0, claims)
+}
+
+/// Decodes, verifies and validates a token, equivalent to
+/// `jsonwebtoken::decode`.
+pub fn decode<T: DeserializeOwned>(
+    token: &str,
+    key: &DecodingKey,
+    validation: &Validation,
+) -> Result<TokenData<T>, Error> {
+    let decoded = crate::decode(token, &key.0, &validation.as_validation_options())?;
+
+    if let Some(ref issuers) = validation.iss {
+        match decoded.payload.get("iss").and_then(|iss| iss.as_str()) {
+            Some(iss) if issuers.contains(iss) => {}
+            _ => return Err(Error::InvalidIssuer),
+        }
+    }
+
+    Ok(TokenData {
+        claims: decoded.deserialize_claims()?,
+        header: decoded.header,
+    })
+}
+
+/// Decodes the header of a token without verifying it, equivalent to
+/// `jsonwebtoken::decode_header`.
+pub fn decode_header(token: &str) -> Result<Header, Error> {
+    crate::decode_only(token).map(|decoded| decoded.header)
+}
@@ -1,13 +1,17 @@
 use crate::validation::validate_header;
 use crate::{
-    pem::PemEncodedKey, validation::validate, Algorithm, Error, Header, SecretKey,
+    pem::PemEncodedKey, validate_claims, Algorithm, Error, Header, JwtVerifier, SecretKey,
     ValidationOptions, VerifyingKey,
 };
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
 use base64::Engine;
 use ecdsa::der::Signature as EcdsaDerSignature;
+use ecdsa::elliptic_curve::bigint::ArrayEncoding;
+use ecdsa::elliptic_curve::Curve;
+use ed25519_dalek::{Signature as Ed25519Signature, VerifyingKey as Ed25519VerifyingKey};
 use hmac::{Hmac, Mac};
 use k256::ecdsa::VerifyingKey as K256VerifyingKey;
-use p256::ecdsa::VerifyingKey as P256VerifyingKey;
 use p384::ecdsa::VerifyingKey as P384VerifyingKey;
 use p521::ecdsa::VerifyingKey as P521VerifyingKey;
 use rsa::signature::Verifier as RsaVerifier;
@@ -15,14 +19,74 @@ use rsa::{
     pkcs1v15::Signature as Pkcs1v15Signature, pkcs1v15::VerifyingKey as Pkcs1v15VerifyingKey,
     pss::VerifyingKey as PssVerifyingKey,
 };
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
 use serde_json::Value as JsonValue;
 use sha2::{Sha256, Sha384, Sha512};
-use simple_asn1::{to_der, ASN1Block, BigUint};
+use simple_asn1::{from_der, to_der, ASN1Block, BigUint};
+use std::collections::HashSet;
+use std::io::Read;
+
+/// base64url engine accepting segments with or without `=` padding, used
+/// when [`ValidationOptions::lenient_base64`] is set.
+const URL_SAFE_LENIENT: GeneralPurpose = GeneralPurpose::new(
+    &base64::alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// The largest token, in bytes, that [`decode_from_reader`] reads.
+pub const MAX_TOKEN_LEN: usize = 64 * 1024;
 
 #[derive(Debug, Clone)]
 pub struct DecodedJwt {
     pub header: Header,
     pub payload: JsonValue,
+    pub(crate) signature: Vec<u8>,
+}
+
+impl DecodedJwt {
+    /// Returns the base64url decoded signature of the token.
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    /// Deserializes the payload into a typed claims structure. Errors name the
+    /// path of the claim that failed to deserialize.
+    pub fn deserialize_claims<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        deserialize_claims(&self.payload)
+    }
+
+    /// Iterates over every claim of the payload as `(name, value)` pairs.
+    pub fn claims_iter(&self) -> impl Iterator<Item = (&str, &JsonValue)> {
+        self.payload
+            .as_object()
+            .into_iter()
+            .flat_map(|claims| claims.iter().map(|(name, value)| (name.as_str(), value)))
+    }
+
+    /// Returns the members of the `aud` claim, whether it is a single string
+    /// or an array, or an empty vector when it is absent. Non-string array
+    /// members are skipped, as during audience validation.
+    pub fn audiences(&self) -> Vec<String> {
+        match self.payload.get("aud") {
+            Some(JsonValue::String(aud)) => vec![aud.clone()],
+            Some(JsonValue::Array(aud)) => aud
+                .iter()
+                .filter_map(JsonValue::as_str)
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Deserializes claims from a JSON payload, reporting the failing claim path
+/// in `Error::ClaimDeserialization`.
+pub(crate) fn deserialize_claims<T: DeserializeOwned>(payload: &JsonValue) -> Result<T, Error> {
+    serde_path_to_error::deserialize(payload).map_err(|err| Error::ClaimDeserialization {
+        path: err.path().to_string(),
+        reason: err.inner().to_string(),
+    })
 }
 
 /// Decodes and validates a JWT using the provided verification key and validation options.
@@ -37,28 +101,453 @@ pub struct DecodedJwt {
 ///
 /// Returns a `DecodedJwt` structure containing the header, payload, and signature if successful,
 /// or an `Error` if decoding or validation fails.
-pub fn decode(
+pub fn decode<V: JwtVerifier + ?Sized>(
+    token: &str,
+    verifying_key: &V,
+    validation_options: &ValidationOptions,
+) -> Result<DecodedJwt, Error> {
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(Error::MalformedToken);
+    }
+
+    let (header, signature) = verify_segments(
+        parts[0],
+        parts[1],
+        parts[2],
+        verifying_key,
+        validation_options,
+    )?;
+    let payload = decode_payload(parts[1], validation_options)?;
+
+    Ok(DecodedJwt {
+        header,
+        payload,
+        signature,
+    })
+}
+
+/// Decodes and validates every token like [`decode`], returning one result
+/// per token in the same order. With the `rayon` feature enabled, the tokens
+/// are verified in parallel.
+///
+/// # Arguments
+///
+/// * `tokens` - The encoded JWT strings.
+/// * `verifying_key` - The key to be used for signature verification, shared by all tokens.
+/// * `options` - The validation options for the claims within the JWTs.
+pub fn verify_batch<V: JwtVerifier + Sync + ?Sized>(
+    tokens: &[&str],
+    verifying_key: &V,
+    validation_options: &ValidationOptions,
+) -> Vec<Result<DecodedJwt, Error>> {
+    #[cfg(feature = "rayon")]
+    use rayon::prelude::*;
+    #[cfg(feature = "rayon")]
+    let tokens = tokens.par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let tokens = tokens.iter();
+
+    tokens
+        .map(|token| decode(token, verifying_key, validation_options))
+        .collect()
+}
+
+/// Decodes and validates a JWT given as bytes, e.g. straight from a network
+/// buffer.
+///
+/// The bytes must be valid UTF-8. Compact JWTs only contain ASCII
+/// characters, so any other character then fails to decode like it would
+/// with [`decode`].
+///
+/// # Arguments
+///
+/// * `token` - The encoded JWT bytes.
+/// * `verifying_key` - The key to be used for signature verification.
+/// * `options` - The validation options for the claims within the JWT.
+///
+/// # Returns
+///
+/// Returns a `DecodedJwt` if successful, `Error::MalformedToken` if the
+/// token isn't valid UTF-8, or an `Error` if decoding or validation fails.
+pub fn decode_bytes<V: JwtVerifier + ?Sized>(
+    token: &[u8],
+    verifying_key: &V,
+    validation_options: &ValidationOptions,
+) -> Result<DecodedJwt, Error> {
+    let token = std::str::from_utf8(token).map_err(|_| Error::MalformedToken)?;
+
+    decode(token, verifying_key, validation_options)
+}
+
+/// Decodes and validates a JWT read from `reader`, e.g. a file or a request
+/// body.
+///
+/// The reader is consumed up to EOF and the token is verified like with
+/// [`decode_bytes`]. Leading and trailing ASCII whitespace, such as a final
+/// newline, is ignored. At most [`MAX_TOKEN_LEN`] bytes are read.
+///
+/// # Arguments
+///
+/// * `reader` - The source of the encoded JWT.
+/// * `verifying_key` - The key to be used for signature verification.
+/// * `options` - The validation options for the claims within the JWT.
+///
+/// # Returns
+///
+/// Returns a `DecodedJwt` if successful, `Error::IoError` if reading fails,
+/// `Error::MalformedToken` if the reader holds more than [`MAX_TOKEN_LEN`]
+/// bytes, or an `Error` if decoding or validation fails.
+pub fn decode_from_reader<R: Read, V: JwtVerifier + ?Sized>(
+    reader: R,
+    verifying_key: &V,
+    validation_options: &ValidationOptions,
+) -> Result<DecodedJwt, Error> {
+    let mut token = Vec::new();
+    reader
+        .take(MAX_TOKEN_LEN as u64 + 1)
+        .read_to_end(&mut token)?;
+    if token.len() > MAX_TOKEN_LEN {
+        return Err(Error::MalformedToken);
+    }
+
+    decode_bytes(token.trim_ascii(), verifying_key, validation_options)
+}
+
+/// The error returned by [`decode_partial`].
+#[derive(Debug, thiserror::Error)]
+#[error("token validation failed")]
+pub struct PartialDecodeError {
+    /// Why the token was rejected.
+    #[source]
+    pub error: Error,
+    /// The decoded token, present only when the signature is valid and a claim
+    /// check failed.
+    pub decoded: Option<Box<DecodedJwt>>,
+}
+
+impl From<PartialDecodeError> for Error {
+    fn from(err: PartialDecodeError) -> Self {
+        err.error
+    }
+}
+
+/// Decodes and validates a JWT like [`decode`], but keeps the decoded token
+/// when the signature is valid and only a claim check fails, e.g. to log who
+/// presented an expired token.
+///
+/// # Arguments
+///
+/// * `token` - The encoded JWT string.
+/// * `verifying_key` - The key to be used for signature verification.
+/// * `options` - The validation options for the claims within the JWT.
+///
+/// # Returns
+///
+/// Returns a `DecodedJwt` if successful, or a `PartialDecodeError` carrying the
+/// verified but rejected token if claim validation fails.
+pub fn decode_partial<V: JwtVerifier + ?Sized>(
+    token: &str,
+    verifying_key: &V,
+    validation_options: &ValidationOptions,
+) -> Result<DecodedJwt, PartialDecodeError> {
+    let without_token = |error| PartialDecodeError {
+        error,
+        decoded: None,
+    };
+
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(without_token(Error::MalformedToken));
+    }
+
+    let (header, signature) = verify_segments(
+        parts[0],
+        parts[1],
+        parts[2],
+        verifying_key,
+        validation_options,
+    )
+    .map_err(without_token)?;
+    let payload = parse_payload(parts[1], validation_options).map_err(without_token)?;
+
+    let decoded = DecodedJwt {
+        header,
+        payload,
+        signature,
+    };
+    match validate_claims(&decoded.payload, validation_options) {
+        Ok(()) => Ok(decoded),
+        Err(error) => Err(PartialDecodeError {
+            error,
+            decoded: Some(Box::new(decoded)),
+        }),
+    }
+}
+
+/// Decodes and validates a JWT, pinning the signature algorithm to `algorithm`.
+///
+/// Unlike the allow-list in `ValidationOptions::algorithms`, the token is only
+/// accepted if its header declares exactly `algorithm`, so the verifier used is
+/// always the one tied to the caller's key.
+///
+/// # Arguments
+///
+/// * `token` - The encoded JWT string.
+/// * `verifying_key` - The key to be used for signature verification.
+/// * `algorithm` - The only algorithm the token may be signed with.
+/// * `options` - The validation options for the claims within the JWT.
+///
+/// # Returns
+///
+/// Returns a `DecodedJwt` if successful, or `Error::InvalidAlgorithm` if the
+/// header declares a different algorithm.
+pub fn decode_with_alg<V: JwtVerifier + ?Sized>(
+    token: &str,
+    verifying_key: &V,
+    algorithm: Algorithm,
+    validation_options: &ValidationOptions,
+) -> Result<DecodedJwt, Error> {
+    let validation_options = ValidationOptions {
+        algorithms: HashSet::from([algorithm]),
+        ..validation_options.clone()
+    };
+    decode(token, verifying_key, &validation_options)
+}
+
+/// Decodes and validates a JWT, inferring the accepted algorithms from the key
+/// when `ValidationOptions::algorithms` is empty.
+///
+/// Ed25519 keys only allow `EdDSA` and HMAC secrets allow `HS256`, `HS384` and
+/// `HS512`. An RSA or EC key can be used with several algorithms of different
+/// strength, so they must be configured explicitly.
+///
+/// # Arguments
+///
+/// * `token` - The encoded JWT string.
+/// * `verifying_key` - The key to be used for signature verification.
+/// * `options` - The validation options for the claims within the JWT.
+///
+/// # Returns
+///
+/// Returns a `DecodedJwt` if successful, or `Error::InvalidAlgorithm` if no
+/// algorithms are configured for an RSA or EC key.
+pub fn decode_auto(
     token: &str,
     verifying_key: &VerifyingKey,
     validation_options: &ValidationOptions,
 ) -> Result<DecodedJwt, Error> {
-    let (header, payload, signature) = split_jwt(token)?;
+    if !validation_options.algorithms.is_empty() {
+        return decode(token, verifying_key, validation_options);
+    }
 
-    validate_header(&header, validation_options)?;
+    let algorithms = match verifying_key {
+        VerifyingKey::EdKey(_) => HashSet::from([Algorithm::EdDSA]),
+        VerifyingKey::Secret(_) => {
+            HashSet::from([Algorithm::HS256, Algorithm::HS384, Algorithm::HS512])
+        }
+        VerifyingKey::RsaKey(_) | VerifyingKey::EcKey(_) => return Err(Error::InvalidAlgorithm),
+    };
+    let validation_options = ValidationOptions {
+        algorithms,
+        ..validation_options.clone()
+    };
+    decode(token, verifying_key, &validation_options)
+}
 
-    let signing_input = format!(
-        "{}.{}",
-        token.split('.').collect::<Vec<&str>>()[0],
-        token.split('.').collect::<Vec<&str>>()[1]
-    );
-    verify_signature(&signing_input, &signature, &header.alg, verifying_key)
-        .map_err(|_| Error::InvalidSignature)?;
-    validate(
-        payload.as_object().ok_or(Error::InvalidKeyFormat)?,
+/// Decodes and validates a nested JWT (RFC 7519, section 5.2), whose payload is
+/// itself a compact JWT.
+///
+/// The outer token is verified with `outer_key` and its payload is then decoded
+/// and validated as an inner token with `inner_key`. The header checks of
+/// `validation_options` apply to both tokens, its claim checks only to the
+/// inner one.
+///
+/// # Arguments
+///
+/// * `token` - The encoded outer JWT string.
+/// * `outer_key` - The key to be used to verify the outer token.
+/// * `inner_key` - The key to be used to verify the inner token.
+/// * `validation_options` - The validation options for the tokens.
+///
+/// # Returns
+///
+/// Returns the `DecodedJwt` of the inner token, or `Error::InvalidNestedJwt` if
+/// the outer payload is not a compact JWT.
+pub fn decode_nested<O: JwtVerifier + ?Sized, I: JwtVerifier + ?Sized>(
+    token: &str,
+    outer_key: &O,
+    inner_key: &I,
+    validation_options: &ValidationOptions,
+) -> Result<DecodedJwt, Error> {
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(Error::MalformedToken);
+    }
+
+    verify_segments(parts[0], parts[1], parts[2], outer_key, validation_options)?;
+
+    let inner_token = decode_segment(parts[1], validation_options)
+        .ok()
+        .and_then(|payload| String::from_utf8(payload).ok())
+        .filter(|inner_token| inner_token.split('.').count() == 3)
+        .ok_or(Error::InvalidNestedJwt)?;
+
+    decode(inner_token.trim(), inner_key, validation_options)
+}
+
+/// Verifies a JWS with a detached payload (RFC 7515, appendix F), where the
+/// payload is transported separately from the header and signature.
+///
+/// The payload is base64url encoded to rebuild the signing input, unless the
+/// header sets `"b64": false` (RFC 7797), in which case it is used as is. Only
+/// the header is checked against `validation_options`, since the payload need
+/// not be a claims set.
+///
+/// RFC 7797, section 6 requires `"b64"` to be listed in `crit` when it is
+/// used, so a header setting `"b64": false` without it is rejected with
+/// `Error::MalformedToken`.
+///
+/// # Arguments
+///
+/// * `protected_header` - The base64url encoded protected header.
+/// * `payload` - The detached payload.
+/// * `signature` - The base64url encoded signature.
+/// * `verifying_key` - The key to be used for signature verification.
+/// * `validation_options` - The validation options for the header.
+///
+/// # Returns
+///
+/// Returns the decoded `Header` if the signature is valid, or an `Error` otherwise.
+pub fn verify_detached<V: JwtVerifier + ?Sized>(
+    protected_header: &str,
+    payload: &[u8],
+    signature: &str,
+    verifying_key: &V,
+    validation_options: &ValidationOptions,
+) -> Result<Header, Error> {
+    let header = parse_header(protected_header, validation_options)?;
+    let payload_segment = if header.get("b64") == Some(&JsonValue::Bool(false)) {
+        let critical = header.crit.as_ref();
+        if !critical.is_some_and(|crit| crit.iter().any(|name| name == "b64")) {
+            return Err(Error::MalformedToken);
+        }
+        std::str::from_utf8(payload)?.to_string()
+    } else {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(payload)
+    };
+
+    verify_segments(
+        protected_header,
+        &payload_segment,
+        signature,
+        verifying_key,
         validation_options,
-    )?;
+    )
+    .map(|(header, _)| header)
+}
+
+/// Decodes and parses the header segment.
+fn parse_header(
+    header_segment: &str,
+    validation_options: &ValidationOptions,
+) -> Result<Header, Error> {
+    let header_data = decode_segment(header_segment, validation_options)?;
+    if validation_options.case_insensitive_alg {
+        Header::from_json_case_insensitive_alg(&header_data)
+    } else {
+        Header::from_json(&header_data)
+    }
+}
+
+/// Decodes a base64url segment, tolerating `=` padding if the options allow
+/// it.
+fn decode_segment(segment: &str, validation_options: &ValidationOptions) -> Result<Vec<u8>, Error> {
+    let decoded = if validation_options.lenient_base64 {
+        URL_SAFE_LENIENT.decode(segment)?
+    } else {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(segment)?
+    };
+    Ok(decoded)
+}
+
+/// Checks the header against the validation options and verifies the
+/// signature over the base64url encoded header and payload segments,
+/// returning the decoded header and signature.
+///
+/// The signing input is built from the segments exactly as received. The
+/// parsed `Header` is never re-serialized, so tokens whose header JSON uses a
+/// different member order or whitespace verify as signed.
+pub(crate) fn verify_segments<V: JwtVerifier + ?Sized>(
+    header_segment: &str,
+    payload_segment: &str,
+    signature_segment: &str,
+    verifying_key: &V,
+    validation_options: &ValidationOptions,
+) -> Result<(Header, Vec<u8>), Error> {
+    let header = parse_header(header_segment, validation_options)?;
+    validate_header(&header, validation_options)?;
+
+    let (signature, fixed_width_signature) =
+        decode_signature(signature_segment, &header.alg, validation_options)?;
+    let signing_input = format!("{}.{}", header_segment, payload_segment);
+    verifying_key
+        .verify(&header.alg, &signing_input, &fixed_width_signature)
+        .map_err(|err| match err {
+            Error::KeyAlgorithmMismatch { .. } => err,
+            _ => Error::InvalidSignature,
+        })?;
+
+    Ok((header, signature))
+}
+
+/// Decodes the signature segment and checks its length for `alg`, returning
+/// the signature as received and in the fixed-width form passed to the
+/// verifier, which differ for DER encoded ECDSA signatures.
+fn decode_signature(
+    signature_segment: &str,
+    alg: &Algorithm,
+    validation_options: &ValidationOptions,
+) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    let signature = decode_segment(signature_segment, validation_options)?;
+    validate_signature_length(&signature, alg, validation_options)?;
+
+    let fixed_width_signature = match ecdsa_signature_len(alg) {
+        Some(signature_len)
+            if validation_options.accept_der_ecdsa_signatures
+                && signature.len() != signature_len =>
+        {
+            der_to_fixed_width_signature(&signature, signature_len)?
+        }
+        _ => signature.clone(),
+    };
+    Ok((signature, fixed_width_signature))
+}
+
+/// Decodes the base64url encoded payload segment and validates its claims.
+pub(crate) fn decode_payload(
+    payload_segment: &str,
+    validation_options: &ValidationOptions,
+) -> Result<JsonValue, Error> {
+    let payload = parse_payload(payload_segment, validation_options)?;
+    validate_claims(&payload, validation_options)?;
+
+    Ok(payload)
+}
 
-    Ok(DecodedJwt { header, payload })
+fn parse_payload(
+    payload_segment: &str,
+    validation_options: &ValidationOptions,
+) -> Result<JsonValue, Error> {
+    let payload_data = decode_segment(payload_segment, validation_options)?;
+    serde_json::from_slice(strip_utf8_bom(&payload_data)).map_err(Error::from)
+}
+
+/// Strips the UTF-8 byte order mark some producers prepend to the header or
+/// payload JSON, which `serde_json` rejects. The signature is always
+/// verified over the segments as received.
+pub(crate) fn strip_utf8_bom(json: &[u8]) -> &[u8] {
+    json.strip_prefix(b"\xef\xbb\xbf").unwrap_or(json)
 }
 
 /// Decodes a JWT without verifying the signature.
@@ -73,26 +562,177 @@ pub fn decode(
 /// Returns a `DecodedJwt` structure containing the header and payload if successful,
 /// or an `Error` if decoding fails.
 pub fn decode_only(token: &str) -> Result<DecodedJwt, Error> {
-    let (header, payload, _) = split_jwt(token)?;
-    Ok(DecodedJwt { header, payload })
+    let (header, payload, signature) = split_jwt(token)?;
+    Ok(DecodedJwt {
+        header,
+        payload,
+        signature,
+    })
+}
+
+/// Verifies only the signature of a JWT, without decoding the payload or
+/// validating any claims. The signature is decoded and its length checked
+/// like with [`decode`] and the default `ValidationOptions`.
+///
+/// # Arguments
+///
+/// * `token` - The encoded JWT string.
+/// * `verifying_key` - The key to be used for signature verification.
+///
+/// # Returns
+///
+/// Returns `Ok(true)` if the signature is valid for the algorithm in the header,
+/// `Ok(false)` if it is not, or an `Error` if the token is malformed, the key
+/// doesn't fit the algorithm or can't be used.
+pub fn verify_signature<V: JwtVerifier + ?Sized>(
+    token: &str,
+    verifying_key: &V,
+) -> Result<bool, Error> {
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(Error::MalformedToken);
+    }
+
+    let validation_options = ValidationOptions::default();
+    let header = parse_header(parts[0], &validation_options)?;
+    let (_, signature) = decode_signature(parts[2], &header.alg, &validation_options)?;
+    let signing_input = format!("{}.{}", parts[0], parts[1]);
+
+    match verifying_key.verify(&header.alg, &signing_input, &signature) {
+        Ok(()) => Ok(true),
+        Err(Error::InvalidSignature) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns the signing input of a JWT, the base64url encoded header and
+/// payload segments joined by `.`, exactly as they are verified. Useful to
+/// debug signature mismatches.
+///
+/// # Arguments
+///
+/// * `token` - The encoded JWT string.
+///
+/// # Returns
+///
+/// Returns the signing input, or `Error::MalformedToken` if the token
+/// doesn't have three segments.
+pub fn signing_input(token: &str) -> Result<String, Error> {
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(Error::MalformedToken);
+    }
+
+    Ok(format!("{}.{}", parts[0], parts[1]))
+}
+
+/// Reads the `kid` header parameter of a JWT without deserializing the full
+/// header or verifying the token. This is intended for selecting the
+/// verification key on hot paths.
+///
+/// # Arguments
+///
+/// * `token` - The encoded JWT string.
+///
+/// # Returns
+///
+/// Returns the `kid` if present, or an `Error` if the header cannot be decoded.
+pub fn peek_kid(token: &str) -> Result<Option<String>, Error> {
+    #[derive(Deserialize)]
+    struct KidOnly {
+        kid: Option<String>,
+    }
+
+    let header_segment = token.split('.').next().ok_or(Error::MalformedToken)?;
+    let header_data = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(header_segment)?;
+    let header: KidOnly = serde_json::from_slice(strip_utf8_bom(&header_data))?;
+
+    Ok(header.kid)
 }
 
 fn split_jwt(token: &str) -> Result<(Header, JsonValue, Vec<u8>), Error> {
     let parts: Vec<&str> = token.split('.').collect();
     if parts.len() != 3 {
-        return Err(Error::InvalidKeyFormat);
+        return Err(Error::MalformedToken);
     }
 
     let header_data = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(parts[0])?;
     let payload_data = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(parts[1])?;
     let signature = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(parts[2])?;
 
-    let header: Header = serde_json::from_slice(&header_data)?;
-    let payload: JsonValue = serde_json::from_slice(&payload_data)?;
+    let header: Header = serde_json::from_slice(strip_utf8_bom(&header_data))?;
+    let payload: JsonValue = serde_json::from_slice(strip_utf8_bom(&payload_data))?;
 
     Ok((header, payload, signature))
 }
 
+/// Rejects signatures whose length doesn't match the fixed width of the
+/// algorithm. DER encoded ECDSA signatures have a variable length, so EC
+/// signatures are not checked when they are accepted.
+fn validate_signature_length(
+    signature: &[u8],
+    alg: &Algorithm,
+    validation_options: &ValidationOptions,
+) -> Result<(), Error> {
+    let expected_len = if validation_options.accept_der_ecdsa_signatures {
+        alg.signature_len()
+            .filter(|_| ecdsa_signature_len(alg).is_none())
+    } else {
+        alg.signature_len()
+    };
+
+    match expected_len {
+        Some(expected_len) if signature.len() != expected_len => Err(Error::InvalidSignatureLength),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the length of the fixed-width `r || s` signature of an ECDSA
+/// algorithm.
+fn ecdsa_signature_len(alg: &Algorithm) -> Option<usize> {
+    alg.signature_len().filter(|_| alg.key_kind() == "EC")
+}
+
+/// Converts an ASN.1 DER encoded ECDSA signature to the fixed-width `r || s`
+/// form of RFC 7518.
+fn der_to_fixed_width_signature(signature: &[u8], signature_len: usize) -> Result<Vec<u8>, Error> {
+    let blocks = from_der(signature).map_err(|_| Error::InvalidSignature)?;
+    let integers = match blocks.as_slice() {
+        [ASN1Block::Sequence(_, integers)] => integers,
+        _ => return Err(Error::InvalidSignature),
+    };
+
+    let mut fixed_width = Vec::with_capacity(signature_len);
+    for integer in integers {
+        let bytes = match integer {
+            ASN1Block::Integer(_, value) => value
+                .to_biguint()
+                .ok_or(Error::InvalidSignature)?
+                .to_bytes_be(),
+            _ => return Err(Error::InvalidSignature),
+        };
+        let bytes = &bytes[bytes.iter().take_while(|byte| **byte == 0).count()..];
+        if bytes.len() > signature_len / 2 {
+            return Err(Error::InvalidSignature);
+        }
+        fixed_width.resize(fixed_width.len() + signature_len / 2 - bytes.len(), 0);
+        fixed_width.extend_from_slice(bytes);
+    }
+
+    if fixed_width.len() == signature_len {
+        Ok(fixed_width)
+    } else {
+        Err(Error::InvalidSignature)
+    }
+}
+
+impl JwtVerifier for VerifyingKey {
+    fn verify(&self, alg: &Algorithm, signing_input: &str, signature: &[u8]) -> Result<(), Error> {
+        alg.check_key_kind(self.key_kind())?;
+        verify_signing_input(signing_input, signature, alg, self)
+    }
+}
+
 /// Verifies the signature of the JWT.
 ///
 /// # Arguments
@@ -105,7 +745,7 @@ fn split_jwt(token: &str) -> Result<(Header, JsonValue, Vec<u8>), Error> {
 /// # Returns
 ///
 /// Returns `Ok(())` if the signature is valid, or an `Error` otherwise.
-fn verify_signature(
+fn verify_signing_input(
     signing_input: &str,
     signature: &[u8],
     alg: &Algorithm,
@@ -133,7 +773,10 @@ fn verify_signature(
             }
             _ => Err(Error::UnsupportedAlgorithm),
         },
-        _ => Err(Error::UnsupportedAlgorithm),
+        VerifyingKey::EdKey(ed_key) => match alg {
+            Algorithm::EdDSA => verify_eddsa(signing_input, signature, ed_key),
+            _ => Err(Error::UnsupportedAlgorithm),
+        },
     }
 }
 
@@ -145,19 +788,26 @@ fn verify_rsa(
     alg: &Algorithm,
 ) -> Result<(), Error> {
     let rsa_public_key = rsa_key.as_rsa_public_key()?;
-    let pkcs1_signature = Pkcs1v15Signature::try_from(signature)?;
+    let pkcs1_signature =
+        Pkcs1v15Signature::try_from(signature).map_err(|_| Error::InvalidSignature)?;
     match alg {
         Algorithm::RS256 => {
             let verifying_key = Pkcs1v15VerifyingKey::<sha2::Sha256>::new(rsa_public_key);
-            verifying_key.verify(signing_input.as_bytes(), &pkcs1_signature)?;
+            verifying_key
+                .verify(signing_input.as_bytes(), &pkcs1_signature)
+                .map_err(|_| Error::InvalidSignature)?;
         }
         Algorithm::RS384 => {
             let verifying_key = Pkcs1v15VerifyingKey::<sha2::Sha384>::new(rsa_public_key);
-            verifying_key.verify(signing_input.as_bytes(), &pkcs1_signature)?;
+            verifying_key
+                .verify(signing_input.as_bytes(), &pkcs1_signature)
+                .map_err(|_| Error::InvalidSignature)?;
         }
         Algorithm::RS512 => {
             let verifying_key = Pkcs1v15VerifyingKey::<sha2::Sha512>::new(rsa_public_key);
-            verifying_key.verify(signing_input.as_bytes(), &pkcs1_signature)?;
+            verifying_key
+                .verify(signing_input.as_bytes(), &pkcs1_signature)
+                .map_err(|_| Error::InvalidSignature)?;
         }
         _ => return Err(Error::UnsupportedAlgorithm),
     };
@@ -173,20 +823,27 @@ fn verify_pss(
     alg: &Algorithm,
 ) -> Result<(), Error> {
     let rsa_public_key = rsa_key.as_rsa_public_key()?;
-    let pss_signature = rsa::pss::Signature::try_from(signature)?;
+    let pss_signature =
+        rsa::pss::Signature::try_from(signature).map_err(|_| Error::InvalidSignature)?;
 
     match alg {
         Algorithm::PS256 => {
             let verifying_key = PssVerifyingKey::<sha2::Sha256>::new(rsa_public_key);
-            verifying_key.verify(signing_input.as_bytes(), &pss_signature)?
+            verifying_key
+                .verify(signing_input.as_bytes(), &pss_signature)
+                .map_err(|_| Error::InvalidSignature)?
         }
         Algorithm::PS384 => {
             let verifying_key = PssVerifyingKey::<sha2::Sha384>::new(rsa_public_key);
-            verifying_key.verify(signing_input.as_bytes(), &pss_signature)?
+            verifying_key
+                .verify(signing_input.as_bytes(), &pss_signature)
+                .map_err(|_| Error::InvalidSignature)?
         }
         Algorithm::PS512 => {
             let veryfing_key = PssVerifyingKey::<sha2::Sha512>::new(rsa_public_key);
-            veryfing_key.verify(signing_input.as_bytes(), &pss_signature)?
+            veryfing_key
+                .verify(signing_input.as_bytes(), &pss_signature)
+                .map_err(|_| Error::InvalidSignature)?
         }
         _ => {
             return Err(Error::UnsupportedAlgorithm);
@@ -237,65 +894,128 @@ fn verify_ecdsa(
     ec_key: &PemEncodedKey,
     alg: &Algorithm,
 ) -> Result<(), Error> {
-    let public_key_bytes = ec_key.as_ec_public_key()?;
+    check_ecdsa_scalar_range(signature, alg)?;
 
     match alg {
         Algorithm::ES256 => {
-            let verifying_key = P256VerifyingKey::from_sec1_bytes(public_key_bytes)
-                .map_err(|_| Error::InvalidEcdsaKey)?;
-            let ecdsa_signature_vec = determine_signature_type(signature, 64);
-            let ecdsa_signature = EcdsaDerSignature::from_bytes(&ecdsa_signature_vec)?;
+            let verifying_key = match ec_key.p256_verifying_key {
+                Some(ref verifying_key) => verifying_key,
+                None => {
+                    ec_key.as_ec_public_key()?;
+                    return Err(Error::InvalidEcdsaKey);
+                }
+            };
+            let ecdsa_signature_vec = determine_signature_type(signature, 64)?;
+            let ecdsa_signature = EcdsaDerSignature::from_bytes(&ecdsa_signature_vec)
+                .map_err(|_| Error::InvalidSignature)?;
             verifying_key
                 .verify(signing_input.as_bytes(), &ecdsa_signature)
                 .map_err(|_| Error::InvalidSignature)
         }
         Algorithm::ES256K => {
+            let public_key_bytes = ec_key.as_ec_public_key()?;
             let verifying_key = K256VerifyingKey::from_sec1_bytes(public_key_bytes)
                 .map_err(|_| Error::InvalidEcdsaKey)?;
-            let ecdsa_signature_vec = determine_signature_type(signature, 64);
-            let ecdsa_signature = EcdsaDerSignature::from_bytes(&ecdsa_signature_vec)?;
+            let ecdsa_signature_vec = determine_signature_type(signature, 64)?;
+            let ecdsa_signature = EcdsaDerSignature::from_bytes(&ecdsa_signature_vec)
+                .map_err(|_| Error::InvalidSignature)?;
             verifying_key
                 .verify(signing_input.as_bytes(), &ecdsa_signature)
                 .map_err(|_| Error::InvalidSignature)
         }
         Algorithm::ES384 => {
+            let public_key_bytes = ec_key.as_ec_public_key()?;
             let verifying_key = P384VerifyingKey::from_sec1_bytes(public_key_bytes)
                 .map_err(|_| Error::InvalidEcdsaKey)?;
-            let ecdsa_signature_vec = determine_signature_type(signature, 96);
-            let ecdsa_signature = EcdsaDerSignature::from_bytes(&ecdsa_signature_vec)?;
+            let ecdsa_signature_vec = determine_signature_type(signature, 96)?;
+            let ecdsa_signature = EcdsaDerSignature::from_bytes(&ecdsa_signature_vec)
+                .map_err(|_| Error::InvalidSignature)?;
             verifying_key
                 .verify(signing_input.as_bytes(), &ecdsa_signature)
                 .map_err(|_| Error::InvalidSignature)
         }
         Algorithm::ES512 => {
+            let public_key_bytes = ec_key.as_ec_public_key()?;
             let verifying_key = P521VerifyingKey::from_sec1_bytes(public_key_bytes)
                 .map_err(|_| Error::InvalidEcdsaKey)?;
-            let ecdsa_signature_vec = determine_signature_type(signature, 132);
-            let ecdsa_signature = EcdsaDerSignature::from_bytes(&ecdsa_signature_vec)?;
+            let ecdsa_signature_vec = determine_signature_type(signature, 132)?;
+            let ecdsa_signature = EcdsaDerSignature::from_bytes(&ecdsa_signature_vec)
+                .map_err(|_| Error::InvalidSignature)?;
             verifying_key
-                .verify(signing_input.as_bytes(), &ecdsa_signature.try_into()?)
+                .verify(
+                    signing_input.as_bytes(),
+                    &ecdsa_signature
+                        .try_into()
+                        .map_err(|_| Error::InvalidSignature)?,
+                )
                 .map_err(|_| Error::InvalidSignature)
         }
         _ => Err(Error::UnsupportedAlgorithm),
     }
 }
 
-fn determine_signature_type(signature: &[u8], signature_len: usize) -> Vec<u8> {
-    // convert signature to DER format if already not in DER format
-    if signature.len() == signature_len {
-        let r = &signature[..signature_len / 2];
-        let s = &signature[signature_len / 2..];
+/// Verifies an Ed25519 signature, which must be the raw 64-byte `R || S`
+/// encoding of RFC 8032, against a raw 32-byte public key.
+fn verify_eddsa(
+    signing_input: &str,
+    signature: &[u8],
+    ed_key: &PemEncodedKey,
+) -> Result<(), Error> {
+    let public_key: &[u8; 32] = ed_key
+        .as_ed_public_key()?
+        .try_into()
+        .map_err(|_| Error::InvalidKeyFormat)?;
+    let verifying_key =
+        Ed25519VerifyingKey::from_bytes(public_key).map_err(|_| Error::InvalidKeyFormat)?;
+    let signature: &[u8; 64] = signature
+        .try_into()
+        .map_err(|_| Error::InvalidSignatureLength)?;
+    let signature = Ed25519Signature::from_bytes(signature);
+    verifying_key
+        .verify(signing_input.as_bytes(), &signature)
+        .map_err(|_| Error::InvalidSignature)
+}
 
-        let asn1_signature = ASN1Block::Sequence(
-            0,
-            vec![
-                ASN1Block::Integer(0, BigUint::from_bytes_be(r).into()),
-                ASN1Block::Integer(0, BigUint::from_bytes_be(s).into()),
-            ],
-        );
+/// Rejects fixed-width ECDSA signatures whose `r` or `s` is zero or not less
+/// than the order of the curve, independently of the checks of the curve
+/// implementations.
+fn check_ecdsa_scalar_range(signature: &[u8], alg: &Algorithm) -> Result<(), Error> {
+    let order = match alg {
+        Algorithm::ES256 => p256::NistP256::ORDER.to_be_byte_array().to_vec(),
+        Algorithm::ES256K => k256::Secp256k1::ORDER.to_be_byte_array().to_vec(),
+        Algorithm::ES384 => p384::NistP384::ORDER.to_be_byte_array().to_vec(),
+        Algorithm::ES512 => p521::NistP521::ORDER.to_be_byte_array().to_vec(),
+        _ => return Err(Error::UnsupportedAlgorithm),
+    };
+    let order = BigUint::from_bytes_be(&order);
 
-        to_der(&asn1_signature).unwrap()
+    let (r, s) = signature.split_at(signature.len() / 2);
+    let in_range = |scalar: &[u8]| {
+        scalar.iter().any(|byte| *byte != 0) && BigUint::from_bytes_be(scalar) < order
+    };
+    if in_range(r) && in_range(s) {
+        Ok(())
     } else {
-        signature.to_vec()
+        Err(Error::InvalidSignature)
+    }
+}
+
+fn determine_signature_type(signature: &[u8], signature_len: usize) -> Result<Vec<u8>, Error> {
+    // convert the fixed-width signature to the DER format expected by the verifier
+    if signature.len() != signature_len {
+        return Err(Error::InvalidSignature);
     }
+
+    let r = &signature[..signature_len / 2];
+    let s = &signature[signature_len / 2..];
+
+    let asn1_signature = ASN1Block::Sequence(
+        0,
+        vec![
+            ASN1Block::Integer(0, BigUint::from_bytes_be(r).into()),
+            ASN1Block::Integer(0, BigUint::from_bytes_be(s).into()),
+        ],
+    );
+
+    Ok(to_der(&asn1_signature).unwrap())
 }
@@ -1,12 +1,15 @@
 use crate::Standard as PemStandard;
 use crate::{Algorithm, Error, SigningKey};
-use base64::Engine;
-use ecdsa::signature::Signer;
+use ecdsa::elliptic_curve::generic_array::ArrayLength;
+use ecdsa::elliptic_curve::CurveArithmetic;
+use ecdsa::signature::{RandomizedSigner, Signer};
+use ecdsa::{PrimeCurve, SignatureSize};
 use k256::pkcs8::DecodePrivateKey;
 use k256::{ecdsa::Signature as K256Signature, ecdsa::SigningKey as K256SigningKey};
 use p256::{ecdsa::Signature as P256Signature, ecdsa::SigningKey as P256SigningKey};
 use p384::{ecdsa::Signature as P384Signature, ecdsa::SigningKey as P384SigningKey};
 use p521::{ecdsa::Signature as P521Signature, ecdsa::SigningKey as P521SigningKey};
+use rand::{CryptoRng, RngCore};
 
 enum EcSigningSchema {
     Es256(P256SigningKey),
@@ -16,35 +19,72 @@ enum EcSigningSchema {
 }
 
 impl EcSigningSchema {
-    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+    /// Signs with deterministic nonces (RFC 6979). When `low_s` is set, `s` is
+    /// normalized to the lower half of the curve order. `k256` always
+    /// normalizes `s`, so ES256K signatures are low-S regardless.
+    fn sign(&self, data: &[u8], low_s: bool) -> Result<Vec<u8>, Error> {
         let signature_bytes = match self {
             EcSigningSchema::Es256(signer) => {
                 let signature: P256Signature = signer.sign(data);
-                // signature.to_der().as_bytes().to_vec()
-                let (r, s) = signature.split_bytes();
-                [r.as_slice(), s.as_slice()].concat()
+                to_fixed_width(signature, low_s)
             }
             EcSigningSchema::Es256k(signer) => {
                 let signature: K256Signature = signer.sign(data);
-                // signature.to_der().as_bytes().to_vec()
-                let (r, s) = signature.split_bytes();
-                [r.as_slice(), s.as_slice()].concat()
+                to_fixed_width(signature, true)
             }
             EcSigningSchema::Es384(signer) => {
                 let signature: P384Signature = signer.sign(data);
-                // signature.to_der().as_bytes().to_vec()
-                let (r, s) = signature.split_bytes();
-                [r.as_slice(), s.as_slice()].concat()
+                to_fixed_width(signature, low_s)
             }
             EcSigningSchema::Es512(signer) => {
                 let signature: P521Signature = signer.sign(data);
-                // signature.to_der().as_bytes().to_vec()
-                let (r, s) = signature.split_bytes();
-                [r.as_slice(), s.as_slice()].concat()
+                to_fixed_width(signature, low_s)
             }
         };
         Ok(signature_bytes)
     }
+
+    /// Signs with hedged nonces drawn from `rng` and the key (RFC 6979,
+    /// section 3.6) instead of purely deterministic ones.
+    fn sign_with_rng<R: CryptoRng + RngCore>(
+        &self,
+        data: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>, Error> {
+        let signature_bytes = match self {
+            EcSigningSchema::Es256(signer) => {
+                let signature: P256Signature = signer.sign_with_rng(rng, data);
+                to_fixed_width(signature, true)
+            }
+            EcSigningSchema::Es256k(signer) => {
+                let signature: K256Signature = signer.sign_with_rng(rng, data);
+                to_fixed_width(signature, true)
+            }
+            EcSigningSchema::Es384(signer) => {
+                let signature: P384Signature = signer.sign_with_rng(rng, data);
+                to_fixed_width(signature, true)
+            }
+            EcSigningSchema::Es512(signer) => {
+                let signature: P521Signature = signer.sign_with_rng(rng, data);
+                to_fixed_width(signature, true)
+            }
+        };
+        Ok(signature_bytes)
+    }
+}
+
+/// Encodes a signature in the fixed-width `r || s` form, normalizing `s` to
+/// the lower half of the curve order first when `low_s` is set.
+fn to_fixed_width<C>(signature: ecdsa::Signature<C>, low_s: bool) -> Vec<u8>
+where
+    C: PrimeCurve + CurveArithmetic,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    let signature = match low_s {
+        true => signature.normalize_s().unwrap_or(signature),
+        false => signature,
+    };
+    signature.to_bytes().to_vec()
 }
 
 fn create_ec_signing_scheme(
@@ -97,9 +137,22 @@ pub(crate) fn sign_es(
     data: &str,
     signing_key: &SigningKey,
     alg: &Algorithm,
-) -> Result<String, Error> {
+    low_s: bool,
+) -> Result<Vec<u8>, Error> {
+    let signing_schema = create_ec_signing_scheme(alg, signing_key)?;
+    let signature = signing_schema.sign(data.as_bytes(), low_s)?;
+
+    Ok(signature)
+}
+
+pub(crate) fn sign_es_with_rng<R: CryptoRng + RngCore>(
+    data: &str,
+    signing_key: &SigningKey,
+    alg: &Algorithm,
+    rng: &mut R,
+) -> Result<Vec<u8>, Error> {
     let signing_schema = create_ec_signing_scheme(alg, signing_key)?;
-    let signature = signing_schema.sign(data.as_bytes())?;
+    let signature = signing_schema.sign_with_rng(data.as_bytes(), rng)?;
 
-    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(signature))
+    Ok(signature)
 }
@@ -0,0 +1,17 @@
+use crate::{Algorithm, Error, SigningKey};
+use ed25519_dalek::pkcs8::DecodePrivateKey;
+use ed25519_dalek::{Signer, SigningKey as Ed25519SigningKey};
+
+pub(crate) fn sign_ed(
+    data: &str,
+    signing_key: &SigningKey,
+    alg: &Algorithm,
+) -> Result<Vec<u8>, Error> {
+    let ed_key = match (signing_key, alg) {
+        (SigningKey::EdKey(key), Algorithm::EdDSA) => key,
+        _ => return Err(Error::UnsupportedAlgorithm),
+    };
+
+    let key = Ed25519SigningKey::from_pkcs8_der(ed_key.as_ed_private_key()?)?;
+    Ok(key.sign(data.as_bytes()).to_bytes().to_vec())
+}
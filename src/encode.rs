@@ -1,5 +1,9 @@
-use crate::{sign_es, sign_hmac, sign_rsa, Algorithm, Error, Header, SigningKey};
+use crate::{
+    decode, sign_ed, sign_es, sign_es_with_rng, sign_hmac, sign_rsa, sign_rsa_with_rng, Algorithm,
+    Error, Header, JwtSigner, JwtVerifier, SigningKey, ValidationOptions,
+};
 use base64::Engine;
+use rand::{CryptoRng, RngCore};
 use serde::Serialize;
 use serde_json::Value as JsonValue;
 
@@ -21,34 +25,207 @@ use serde_json::Value as JsonValue;
 /// let encoded = encode(&header, &signing_key, &payload);
 /// assert!(encoded.is_ok());
 /// ```
-pub fn encode<T: Serialize>(
+pub fn encode<T: Serialize, S: JwtSigner + ?Sized>(
+    header: &Header,
+    signing_key: &S,
+    payload: &T,
+) -> Result<String, Error> {
+    if signing_key
+        .algorithm()
+        .is_some_and(|algorithm| algorithm != header.alg)
+    {
+        return Err(Error::InvalidAlgorithm);
+    }
+
+    encode_with_signer(header, payload, |signing_input| {
+        signing_key.sign(&header.alg, signing_input)
+    })
+}
+
+/// Encodes a JWT like [`encode`], drawing the randomness of the signature from
+/// `rng` instead of the OS RNG.
+///
+/// A seeded RNG makes RSA-PSS signatures reproducible, e.g. in tests. ECDSA
+/// nonces are derived from `rng` and the key (RFC 6979, section 3.6), and
+/// HMAC, RSA PKCS#1 v1.5 and EdDSA signatures don't use randomness.
+///
+/// # Arguments
+/// * `header` - The header of the JWT.
+/// * `signing_key` - The signing key used to sign the JWT.
+/// * `payload` - The payload of the JWT.
+/// * `rng` - The cryptographically secure RNG used for signing.
+pub fn encode_with_rng<T: Serialize, R: CryptoRng + RngCore>(
     header: &Header,
     signing_key: &SigningKey,
     payload: &T,
+    rng: &mut R,
+) -> Result<String, Error> {
+    encode_with_signer(header, payload, |signing_input| {
+        signing_key.sign_with_rng(&header.alg, signing_input, rng)
+    })
+}
+
+/// Options controlling how [`encode_with_options`] signs a JWT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodeOptions {
+    /// Normalize the `s` component of ECDSA signatures to the lower half of
+    /// the curve order. Enabled by default. ES256K signatures are always
+    /// low-S, since the `k256` signer normalizes them itself.
+    pub ecdsa_low_s_normalization: bool,
+}
+
+impl EncodeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether ECDSA signatures are normalized to low-S. Disabling it
+    /// emits `s` as computed, for verifiers that compare signatures raw. It
+    /// has no effect on ES256K.
+    pub fn ecdsa_low_s_normalization(mut self, ecdsa_low_s_normalization: bool) -> Self {
+        self.ecdsa_low_s_normalization = ecdsa_low_s_normalization;
+        self
+    }
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self {
+            ecdsa_low_s_normalization: true,
+        }
+    }
+}
+
+/// Encodes a JWT like [`encode`], signing it as configured by `options`.
+///
+/// # Arguments
+/// * `header` - The header of the JWT.
+/// * `signing_key` - The signing key used to sign the JWT.
+/// * `payload` - The payload of the JWT.
+/// * `options` - How the JWT is signed.
+pub fn encode_with_options<T: Serialize>(
+    header: &Header,
+    signing_key: &SigningKey,
+    payload: &T,
+    options: &EncodeOptions,
+) -> Result<String, Error> {
+    encode_with_signer(header, payload, |signing_input| {
+        signing_key.sign_with_options(&header.alg, signing_input, options)
+    })
+}
+
+fn encode_with_signer<T: Serialize>(
+    header: &Header,
+    payload: &T,
+    sign: impl FnOnce(&str) -> Result<Vec<u8>, Error>,
 ) -> Result<String, Error> {
-    let header_json = serde_json::to_value(header)?;
     let payload_json = serde_json::to_value(payload)?;
-    let signing_input = get_signing_input(&payload_json, &header_json)?;
+    let signing_input = get_signing_input(&payload_json, header)?;
+    let signature = sign(&signing_input)?;
+
+    Ok(format!(
+        "{}.{}",
+        signing_input,
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(signature)
+    ))
+}
 
-    let signature = match header.alg {
-        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
-            sign_hmac(&signing_input, signing_key, &header.alg)
+impl JwtSigner for SigningKey {
+    fn sign(&self, alg: &Algorithm, signing_input: &str) -> Result<Vec<u8>, Error> {
+        alg.check_key_kind(self.key_kind())?;
+
+        match alg {
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                sign_hmac(signing_input, self, alg)
+            }
+            Algorithm::RS256
+            | Algorithm::RS384
+            | Algorithm::RS512
+            | Algorithm::PS256
+            | Algorithm::PS384
+            | Algorithm::PS512 => sign_rsa(signing_input, self, alg),
+            Algorithm::ES256 | Algorithm::ES256K | Algorithm::ES384 | Algorithm::ES512 => {
+                sign_es(signing_input, self, alg, true)
+            }
+            Algorithm::EdDSA => sign_ed(signing_input, self, alg),
         }
-        Algorithm::RS256
-        | Algorithm::RS384
-        | Algorithm::RS512
-        | Algorithm::PS256
-        | Algorithm::PS384
-        | Algorithm::PS512 => sign_rsa(&signing_input, signing_key, &header.alg),
-        Algorithm::ES256 | Algorithm::ES256K | Algorithm::ES384 | Algorithm::ES512 => {
-            sign_es(&signing_input, signing_key, &header.alg)
+    }
+}
+
+impl SigningKey {
+    fn sign_with_options(
+        &self,
+        alg: &Algorithm,
+        signing_input: &str,
+        options: &EncodeOptions,
+    ) -> Result<Vec<u8>, Error> {
+        match alg {
+            Algorithm::ES256 | Algorithm::ES256K | Algorithm::ES384 | Algorithm::ES512 => {
+                alg.check_key_kind(self.key_kind())?;
+                sign_es(signing_input, self, alg, options.ecdsa_low_s_normalization)
+            }
+            _ => self.sign(alg, signing_input),
+        }
+    }
+
+    fn sign_with_rng<R: CryptoRng + RngCore>(
+        &self,
+        alg: &Algorithm,
+        signing_input: &str,
+        rng: &mut R,
+    ) -> Result<Vec<u8>, Error> {
+        alg.check_key_kind(self.key_kind())?;
+
+        match alg {
+            Algorithm::RS256
+            | Algorithm::RS384
+            | Algorithm::RS512
+            | Algorithm::PS256
+            | Algorithm::PS384
+            | Algorithm::PS512 => sign_rsa_with_rng(signing_input, self, alg, rng),
+            Algorithm::ES256 | Algorithm::ES256K | Algorithm::ES384 | Algorithm::ES512 => {
+                sign_es_with_rng(signing_input, self, alg, rng)
+            }
+            _ => self.sign(alg, signing_input),
         }
-    }?;
+    }
+}
 
-    Ok(format!("{}.{}", signing_input, signature))
+/// Verifies and validates `token`, lets `mutate_claims` modify its claims and
+/// signs them again as a new token, as done by token exchange gateways.
+///
+/// The new token gets a fresh header for `signing_alg`, so header parameters of
+/// the incoming token such as `kid` are not carried over.
+///
+/// # Arguments
+///
+/// * `token` - The encoded JWT string to exchange.
+/// * `verifying_key` - The key used to verify `token`.
+/// * `validation_options` - The validation options for the claims within `token`.
+/// * `mutate_claims` - Applied to the claims before they are signed again.
+/// * `signing_key` - The key used to sign the new token.
+/// * `signing_alg` - The algorithm of the new token.
+pub fn reissue<V, S, F>(
+    token: &str,
+    verifying_key: &V,
+    validation_options: &ValidationOptions,
+    mutate_claims: F,
+    signing_key: &S,
+    signing_alg: Algorithm,
+) -> Result<String, Error>
+where
+    V: JwtVerifier + ?Sized,
+    S: JwtSigner + ?Sized,
+    F: FnOnce(&mut JsonValue),
+{
+    let mut claims = decode(token, verifying_key, validation_options)?.payload;
+    mutate_claims(&mut claims);
+    encode(&Header::new(signing_alg), signing_key, &claims)
 }
 
-fn get_signing_input(payload: &JsonValue, header: &JsonValue) -> Result<String, Error> {
+fn get_signing_input<H: Serialize>(payload: &JsonValue, header: &H) -> Result<String, Error> {
+    // The header is serialized directly rather than through a `Value`, whose
+    // map would reorder its members alphabetically.
     let header_str = serde_json::to_string(header)?;
     let payload_str = serde_json::to_string(payload)?;
     let signing_input = format!(
@@ -1,26 +1,43 @@
 use serde_json as SerdeError;
 use thiserror::Error;
 
+/// Errors returned by this crate. Variants wrapping an error of an underlying
+/// crate return it from [`std::error::Error::source`].
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Invalid key format")]
     InvalidKeyFormat,
+    #[error("Malformed token")]
+    MalformedToken,
     #[error("Invalid Ecdsa key")]
     InvalidEcdsaKey,
     #[error("ASN1 decoder error")]
     RsaError(#[from] simple_asn1::ASN1DecodeErr),
-    #[error("PEM parser error: {0}")]
+    #[error("PEM parser error: {}", pem_error_reason(.0))]
     PemError(#[from] pem::PemError),
     #[error("Serde decode error")]
     SerdeEncodeDecodeError(#[from] SerdeError::Error),
     #[error("Base64 decode error")]
     Base64EncodeDecodeError(#[from] base64::DecodeError),
+    #[error("Encrypted PEM keys are not supported")]
+    EncryptedKeyUnsupported,
     #[error("Unsupported algorithm")]
     UnsupportedAlgorithm,
+    #[error("Key of kind {key_kind} cannot be used with algorithm {alg}")]
+    KeyAlgorithmMismatch {
+        alg: crate::Algorithm,
+        key_kind: String,
+    },
+    #[error("Unsupported key type: {0}")]
+    UnsupportedKeyType(String),
+    #[error("Key is declared for encryption and cannot be used for signatures")]
+    KeyNotForSigning,
     #[error("Hmac invalid length")]
     HmacInvalidLength(#[from] hmac::digest::InvalidLength),
     #[error("UTF-8 conversion error: {0}")]
     Utf8Error(#[from] std::str::Utf8Error),
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
     #[error("PKCS8 error: {0}")]
     Pkcs8Error(#[from] rsa::pkcs8::Error),
     #[error("PKCS1 error: {0}")]
@@ -31,10 +48,15 @@ pub enum Error {
     InvalidEcdsaKeyType(#[from] ecdsa::Error),
     #[error("RSA error: {0}")]
     InvalidRsaKey(#[from] rsa::errors::Error),
+    #[cfg(feature = "pkcs12")]
+    #[error("PKCS#12 error: {0}")]
+    Pkcs12Error(#[from] p12_keystore::error::Error),
     #[error("Pkcs8 Spki error: {0}")]
     Pkcs8SpkiError(#[from] k256::pkcs8::spki::Error),
     #[error("Expired signature")]
     ExpiredSignature,
+    #[error("Token too old: issued longer ago than the maximum age")]
+    TokenTooOld,
     #[error("Immature signature")]
     ImmatureSignature,
     #[error("Invalid issuer")]
@@ -43,10 +65,218 @@ pub enum Error {
     InvalidSubject,
     #[error("Invalid audience")]
     InvalidAudience,
+    #[error("Invalid audience: missing required audience {0}")]
+    MissingRequiredAudience(String),
     #[error("Invalid algorithm")]
     InvalidAlgorithm,
+    #[error("Invalid token type")]
+    InvalidType,
+    #[error("Unknown header parameter: {0}")]
+    UnknownHeaderParameter(String),
+    #[error(
+        "Certificate thumbprint mismatch: x5t or x5t#S256 doesn't match the x5c leaf certificate"
+    )]
+    CertificateThumbprintMismatch,
     #[error("Invalid signature")]
     InvalidSignature,
+    #[error("Invalid signature length")]
+    InvalidSignatureLength,
+    #[error("Invalid nested JWT: payload is not a compact JWT")]
+    InvalidNestedJwt,
+    #[error("Unknown key id: {0}")]
+    UnknownKeyId(String),
+    #[error("No key identifier in header")]
+    MissingKeyId,
+    #[error("JWK Set URL not allowed: {0}")]
+    JkuNotAllowed(String),
     #[error("Invalid claim: {0}")]
     InvalidClaim(String),
+    #[error("Missing required claim: {0}")]
+    MissingRequiredClaim(String),
+    #[error("Claim validation failed: {0}")]
+    ClaimValidation(String),
+    #[error("Failed to deserialize claims at `{path}`: {reason}")]
+    ClaimDeserialization { path: String, reason: String },
+}
+
+impl Error {
+    /// Returns `true` if the token has expired.
+    pub fn is_expired(&self) -> bool {
+        matches!(self, Error::ExpiredSignature)
+    }
+
+    /// Returns `true` if the token is not valid yet.
+    pub fn is_immature(&self) -> bool {
+        matches!(self, Error::ImmatureSignature)
+    }
+
+    /// Returns `true` if the signature failed to verify or had the wrong length.
+    pub fn is_invalid_signature(&self) -> bool {
+        matches!(
+            self,
+            Error::InvalidSignature | Error::InvalidSignatureLength
+        )
+    }
+
+    /// Returns `true` if the audience was rejected or a required audience is
+    /// missing.
+    pub fn is_invalid_audience(&self) -> bool {
+        matches!(
+            self,
+            Error::InvalidAudience | Error::MissingRequiredAudience(_)
+        )
+    }
+
+    /// Returns `true` if the issuer was rejected.
+    pub fn is_invalid_issuer(&self) -> bool {
+        matches!(self, Error::InvalidIssuer)
+    }
+
+    /// Returns `true` if the algorithm of the token is not allowed.
+    pub fn is_invalid_algorithm(&self) -> bool {
+        matches!(self, Error::InvalidAlgorithm)
+    }
+
+    /// Suggests an HTTP status code for responding to a request whose token
+    /// failed with this error: `401` when the token was rejected, `400` when
+    /// it is malformed and `500` when the key or its configuration is at
+    /// fault.
+    pub fn status_hint(&self) -> u16 {
+        match self {
+            Error::ExpiredSignature
+            | Error::TokenTooOld
+            | Error::ImmatureSignature
+            | Error::InvalidIssuer
+            | Error::InvalidSubject
+            | Error::InvalidAudience
+            | Error::MissingRequiredAudience(_)
+            | Error::InvalidAlgorithm
+            | Error::KeyAlgorithmMismatch { .. }
+            | Error::InvalidType
+            | Error::UnknownHeaderParameter(_)
+            | Error::CertificateThumbprintMismatch
+            | Error::InvalidSignature
+            | Error::InvalidSignatureLength
+            | Error::UnknownKeyId(_)
+            | Error::MissingKeyId
+            | Error::JkuNotAllowed(_)
+            | Error::InvalidClaim(_)
+            | Error::MissingRequiredClaim(_)
+            | Error::ClaimValidation(_) => 401,
+            Error::MalformedToken
+            | Error::SerdeEncodeDecodeError(_)
+            | Error::Base64EncodeDecodeError(_)
+            | Error::Utf8Error(_)
+            | Error::UnsupportedAlgorithm
+            | Error::InvalidNestedJwt
+            | Error::ClaimDeserialization { .. } => 400,
+            Error::InvalidKeyFormat
+            | Error::InvalidEcdsaKey
+            | Error::RsaError(_)
+            | Error::PemError(_)
+            | Error::EncryptedKeyUnsupported
+            | Error::UnsupportedKeyType(_)
+            | Error::KeyNotForSigning
+            | Error::HmacInvalidLength(_)
+            | Error::Pkcs8Error(_)
+            | Error::Pkcs1Error(_)
+            | Error::InvalidRsaKeyType(_)
+            | Error::InvalidEcdsaKeyType(_)
+            | Error::InvalidRsaKey(_)
+            | Error::Pkcs8SpkiError(_)
+            | Error::IoError(_) => 500,
+            #[cfg(feature = "pkcs12")]
+            Error::Pkcs12Error(_) => 500,
+        }
+    }
+}
+
+/// Describes a PEM parsing failure without echoing any of the input, since
+/// the `pem` crate includes header values and offending base64 bytes in its
+/// own messages.
+fn pem_error_reason(err: &pem::PemError) -> &'static str {
+    match err {
+        pem::PemError::MismatchedTags(_, _) => "mismatched BEGIN and END tags",
+        pem::PemError::MalformedFraming => "malformed framing",
+        pem::PemError::MissingBeginTag => "missing BEGIN tag",
+        pem::PemError::MissingEndTag => "missing END tag",
+        pem::PemError::MissingData => "missing data",
+        pem::PemError::InvalidData(_) => "invalid base64 data",
+        pem::PemError::InvalidHeader(_) => "invalid header",
+        pem::PemError::NotUtf8(_) => "invalid UTF-8",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn test_error_source() {
+        let err = crate::base64url_decode("Zg==").unwrap_err();
+        assert!(matches!(err, Error::Base64EncodeDecodeError(_)));
+        let source = err.source().expect("base64 error should have a source");
+        assert!(source.downcast_ref::<base64::DecodeError>().is_some());
+        assert!(source.source().is_none());
+
+        let err = crate::decode_partial(
+            "not base64!.e30.c2ln",
+            &crate::VerifyingKey::from_secret(b"mysecret"),
+            &crate::ValidationOptions::default(),
+        )
+        .unwrap_err();
+        let mut chain = vec![err.to_string()];
+        let mut source = err.source();
+        while let Some(err) = source {
+            chain.push(err.to_string());
+            source = err.source();
+        }
+        assert_eq!(chain.len(), 3);
+        assert_eq!(chain[1], "Base64 decode error");
+
+        assert!(Error::InvalidSignature.source().is_none());
+    }
+
+    #[test]
+    fn test_error_predicates() {
+        assert!(Error::ExpiredSignature.is_expired());
+        assert!(!Error::ImmatureSignature.is_expired());
+
+        assert!(Error::ImmatureSignature.is_immature());
+        assert!(!Error::ExpiredSignature.is_immature());
+
+        assert!(Error::InvalidSignature.is_invalid_signature());
+        assert!(Error::InvalidSignatureLength.is_invalid_signature());
+        assert!(!Error::InvalidAlgorithm.is_invalid_signature());
+
+        assert!(Error::InvalidAudience.is_invalid_audience());
+        assert!(Error::MissingRequiredAudience("api".to_string()).is_invalid_audience());
+        assert!(!Error::InvalidIssuer.is_invalid_audience());
+
+        assert!(Error::InvalidIssuer.is_invalid_issuer());
+        assert!(!Error::InvalidSubject.is_invalid_issuer());
+
+        assert!(Error::InvalidAlgorithm.is_invalid_algorithm());
+        assert!(!Error::UnsupportedAlgorithm.is_invalid_algorithm());
+    }
+
+    #[test]
+    fn test_status_hint() {
+        assert_eq!(Error::ExpiredSignature.status_hint(), 401);
+        assert_eq!(Error::InvalidSignature.status_hint(), 401);
+
+        let malformed = crate::decode_only("not-a-token.e30.c2ln").unwrap_err();
+        assert_eq!(malformed.status_hint(), 400);
+
+        let verifying_key = crate::VerifyingKey::from_secret(b"secret");
+        let options = crate::ValidationOptions::default();
+        let malformed = crate::decode("not-a-token", &verifying_key, &options).unwrap_err();
+        assert!(matches!(malformed, Error::MalformedToken));
+        assert_eq!(malformed.status_hint(), 400);
+
+        let key_error = crate::VerifyingKey::from_rsa_pem(b"not a key").unwrap_err();
+        assert_eq!(key_error.status_hint(), 500);
+        assert_eq!(Error::InvalidKeyFormat.status_hint(), 500);
+    }
 }
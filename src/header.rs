@@ -1,42 +1,55 @@
 use base64::Engine;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
+use crate::decode::strip_utf8_bom;
 use crate::Algorithm;
 use crate::Error;
+use crate::Jwk;
 
+/// The JOSE header of a JWT.
+///
+/// Members are serialized in declaration order, so encoded headers always
+/// start with `alg`, `typ`, `cty` and `kid`, followed by the remaining
+/// members that are set and finally the entries of `extras`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Header {
     pub alg: Algorithm,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub jku: Option<String>,
+    pub typ: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub jwk: Option<Jwk>,
+    pub cty: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub kid: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub x5u: Option<String>,
+    pub jku: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub x5c: Option<Vec<String>>,
+    pub jwk: Option<Jwk>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub x5t: Option<String>,
+    pub x5u: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub x5t_s256: Option<String>,
+    pub x5c: Option<Vec<String>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub typ: Option<String>,
+    pub x5t: Option<String>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub cty: Option<String>,
+    #[serde(rename = "x5t#S256", skip_serializing_if = "Option::is_none")]
+    pub x5t_s256: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub crit: Option<Vec<String>>,
+
+    /// Header parameters without a typed field, such as `epk`, `apu` and
+    /// `apv`.
+    #[serde(flatten)]
+    pub extras: Map<String, Value>,
 }
 
 impl Header {
@@ -53,82 +66,106 @@ impl Header {
             typ: Some("JWT".to_string()),
             cty: None,
             crit: None,
+            extras: Map::new(),
         }
     }
 
     pub fn from_encoded(encoded: &[u8]) -> Result<Self, Error> {
         let decoded_from_b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(encoded)?;
-        serde_json::from_slice(decoded_from_b64.as_slice()).map_err(Error::from)
+        Self::from_json(&decoded_from_b64)
     }
-}
 
-impl Default for Header {
-    fn default() -> Self {
-        Self::new(Algorithm::HS256)
+    /// Like [`from_encoded`](Self::from_encoded), but accepts an `alg` that
+    /// only differs in ASCII case from a supported algorithm, e.g. `rs256`.
+    /// The parsed header holds the canonical algorithm.
+    pub fn from_encoded_case_insensitive_alg(encoded: &[u8]) -> Result<Self, Error> {
+        let decoded_from_b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(encoded)?;
+        Self::from_json_case_insensitive_alg(&decoded_from_b64)
     }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Jwk {
-    pub kty: String,
-
-    #[serde(rename = "use", skip_serializing_if = "Option::is_none")]
-    pub key_use: Option<String>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub key_ops: Option<Vec<String>>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub alg: Option<String>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub kid: Option<String>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub x5u: Option<String>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub x5c: Option<Vec<String>>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub x5t: Option<String>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub x5t_s256: Option<String>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub crv: Option<String>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub x: Option<String>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub y: Option<String>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub d: Option<String>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub n: Option<String>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub e: Option<String>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub p: Option<String>,
+    pub(crate) fn from_json_case_insensitive_alg(decoded_from_b64: &[u8]) -> Result<Self, Error> {
+        let mut header: Value = serde_json::from_slice(strip_utf8_bom(decoded_from_b64))?;
+        if let Some(alg) = header.get_mut("alg") {
+            if let Some(canonical) = alg.as_str().and_then(Algorithm::from_str_ignore_ascii_case) {
+                *alg = Value::String(canonical.to_string());
+            }
+        }
+        Self::from_json(&serde_json::to_vec(&header)?)
+    }
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub q: Option<String>,
+    pub(crate) fn from_json(decoded_from_b64: &[u8]) -> Result<Self, Error> {
+        let decoded_from_b64 = strip_utf8_bom(decoded_from_b64);
+        serde_json::from_slice(decoded_from_b64).map_err(|err| {
+            // Report an `alg` this crate doesn't implement distinctly from a
+            // malformed header.
+            let header: Option<Value> = serde_json::from_slice(decoded_from_b64).ok();
+            match header
+                .as_ref()
+                .and_then(|header| header.get("alg")?.as_str())
+            {
+                Some(alg) if alg.parse::<Algorithm>().is_err() => Error::UnsupportedAlgorithm,
+                _ => Error::from(err),
+            }
+        })
+    }
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub dp: Option<String>,
+    /// Returns the value of a header parameter that has no typed field.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.extras.get(key)
+    }
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub dq: Option<String>,
+    /// Returns the `jku` (JWK Set URL) header if it is an `https` URL whose
+    /// host is one of `allowed_hosts`, or `Ok(None)` if the header is unset.
+    ///
+    /// `jku` is chosen by whoever crafted the token, so fetching it without
+    /// this check allows server-side request forgery. Hosts are compared
+    /// exactly, ignoring ASCII case, and URLs with user info are refused.
+    /// Fetching the JWK Set is left to the caller.
+    pub fn allowed_jku(&self, allowed_hosts: &[&str]) -> Result<Option<&str>, Error> {
+        let Some(jku) = self.jku.as_deref() else {
+            return Ok(None);
+        };
+        let not_allowed = || Error::JkuNotAllowed(jku.to_string());
+
+        let scheme_len = "https://".len();
+        if !jku
+            .get(..scheme_len)
+            .is_some_and(|scheme| scheme.eq_ignore_ascii_case("https://"))
+        {
+            return Err(not_allowed());
+        }
+        let rest = &jku[scheme_len..];
+        let authority = &rest[..rest.find(['/', '?', '#']).unwrap_or(rest.len())];
+        if authority.contains(['@', '\\']) {
+            return Err(not_allowed());
+        }
+        let host = match authority.rfind(':') {
+            Some(port_start) if !authority[port_start..].contains(']') => &authority[..port_start],
+            _ => authority,
+        };
+
+        if !host.is_empty()
+            && allowed_hosts
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(host))
+        {
+            Ok(Some(jku))
+        } else {
+            Err(not_allowed())
+        }
+    }
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub qi: Option<String>,
+    /// Returns `true` if the `cty` header marks the payload as a nested JWT
+    /// (RFC 7519, section 5.2). Decoding the inner token is left to the caller.
+    pub fn is_nested_jwt(&self) -> bool {
+        self.cty
+            .as_deref()
+            .is_some_and(|cty| cty.eq_ignore_ascii_case("JWT"))
+    }
+}
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub k: Option<String>,
+impl Default for Header {
+    fn default() -> Self {
+        Self::new(Algorithm::HS256)
+    }
 }
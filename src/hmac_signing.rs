@@ -1,5 +1,4 @@
 use crate::{Algorithm, Error, SigningKey};
-use base64::Engine;
 use hmac::{Hmac, Mac};
 use sha2::{Sha256, Sha384, Sha512};
 
@@ -11,7 +10,7 @@ pub(crate) fn sign_hmac(
     data: &str,
     signing_key: &SigningKey,
     alg: &Algorithm,
-) -> Result<String, Error> {
+) -> Result<Vec<u8>, Error> {
     let key = match signing_key {
         SigningKey::Secret(secret) => secret.inner(),
         _ => return Err(Error::UnsupportedAlgorithm),
@@ -36,5 +35,5 @@ pub(crate) fn sign_hmac(
         _ => return Err(Error::UnsupportedAlgorithm),
     };
 
-    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(signature))
+    Ok(signature)
 }
@@ -0,0 +1,324 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Header, VerifyingKey};
+
+/// A JSON Web Key (RFC 7517) with the key material members defined in RFC 7518.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+
+    #[serde(rename = "use", skip_serializing_if = "Option::is_none")]
+    pub key_use: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_ops: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x5u: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x5c: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x5t: Option<String>,
+
+    #[serde(rename = "x5t#S256", skip_serializing_if = "Option::is_none")]
+    pub x5t_s256: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub d: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dp: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dq: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qi: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oth: Option<Vec<OtherPrimeInfo>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub k: Option<String>,
+}
+
+impl Jwk {
+    /// Fails with [`Error::KeyNotForSigning`] if the key is declared for
+    /// encryption, with `"use": "enc"` or an RSA encryption `alg` such as
+    /// `RSA-OAEP`.
+    pub(crate) fn check_signature_use(&self) -> Result<(), Error> {
+        let encryption_alg = self
+            .alg
+            .as_deref()
+            .is_some_and(|alg| alg.starts_with("RSA-OAEP") || alg == "RSA1_5");
+        if self.key_use.as_deref() == Some("enc") || encryption_alg {
+            Err(Error::KeyNotForSigning)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Additional prime information of a multi-prime RSA key (RFC 7518, section 6.3.2.7).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OtherPrimeInfo {
+    pub r: String,
+    pub d: String,
+    pub t: String,
+}
+
+/// A JWK Set (RFC 7517, section 5), as published on a JWKS endpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+impl JwkSet {
+    /// Returns the key whose `kid` is `kid`, or [`Error::UnknownKeyId`].
+    ///
+    /// Every key is compared in full, so the time taken doesn't reveal
+    /// whether or where a key matched.
+    pub fn find(&self, kid: &str) -> Result<&Jwk, Error> {
+        self.find_by(kid, |jwk| jwk.kid.as_deref())
+    }
+
+    /// Returns the key whose SHA-1 certificate thumbprint (`x5t`) is `x5t`,
+    /// or [`Error::UnknownKeyId`].
+    pub fn find_by_x5t(&self, x5t: &str) -> Result<&Jwk, Error> {
+        self.find_by(x5t, |jwk| jwk.x5t.as_deref())
+    }
+
+    /// Returns the key identified by the `kid` of `header`, falling back to
+    /// its `x5t#S256` and then its `x5t` thumbprint when `kid` is absent, as
+    /// some older providers only identify keys by thumbprint. Returns
+    /// [`Error::MissingKeyId`] if the header has none of them.
+    pub fn find_for_header(&self, header: &Header) -> Result<&Jwk, Error> {
+        if let Some(kid) = header.kid.as_deref() {
+            self.find(kid)
+        } else if let Some(x5t_s256) = header.x5t_s256.as_deref() {
+            self.find_by(x5t_s256, |jwk| jwk.x5t_s256.as_deref())
+        } else if let Some(x5t) = header.x5t.as_deref() {
+            self.find_by_x5t(x5t)
+        } else {
+            Err(Error::MissingKeyId)
+        }
+    }
+
+    /// Builds the verifying key whose `kid` is `kid`.
+    pub fn verifying_key(&self, kid: &str) -> Result<VerifyingKey, Error> {
+        VerifyingKey::try_from(self.find(kid)?)
+    }
+
+    /// Builds the verifying key identified by `header`, see
+    /// [`find_for_header`](Self::find_for_header).
+    pub fn verifying_key_for_header(&self, header: &Header) -> Result<VerifyingKey, Error> {
+        VerifyingKey::try_from(self.find_for_header(header)?)
+    }
+
+    /// Returns the key whose member selected by `field` is `id`. Every key is
+    /// compared in full.
+    fn find_by<F>(&self, id: &str, field: F) -> Result<&Jwk, Error>
+    where
+        F: Fn(&Jwk) -> Option<&str>,
+    {
+        self.keys
+            .iter()
+            .fold(None, |found, jwk| {
+                let matches = field(jwk)
+                    .is_some_and(|candidate| constant_time_eq(candidate.as_bytes(), id.as_bytes()));
+                found.or(Some(jwk).filter(|_| matches))
+            })
+            .ok_or_else(|| Error::UnknownKeyId(id.to_string()))
+    }
+}
+
+/// Compares two byte strings without exiting early on the first difference.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_google_jwk() {
+        let jwk: Jwk = serde_json::from_str(
+            r#"{
+                "e": "AQAB",
+                "kty": "RSA",
+                "alg": "RS256",
+                "n": "vdtZ3cfuh44JlWkJRu-3yddVp58zxSHwsWiW_jpaXgpebo0an7qY2IEs3D7kC186Bwi0T7Km9mUcDbxod89IbtZuQQuhxlgaXB-qX9GokNLdqg69rUaealXGrCdKOQ-rOBlNNGn3M4KywEC98KyQAKXe7prs7yGqI_434rrULaE7ZFmLAzsYNoZ_8l53SGDiRaUrZkhxXOEhlv1nolgYGIH2lkhEZ5BlU53BfzwjO-bLeMwxJIZxSIOy8EBIMLP7eVu6AIkAr9MaDPJqeF7n7Cn8yv_qmy51bV-INRS-HKRVriSoUxhQQTbvDYYvJzHGYu_ciJ4oRYKkDEwxXztUew",
+                "use": "sig",
+                "kid": "b2620d5e7f132b52afe8875cdf3776c064249d04"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(jwk.kty, "RSA");
+        assert_eq!(jwk.alg.as_deref(), Some("RS256"));
+        assert_eq!(jwk.key_use.as_deref(), Some("sig"));
+        assert_eq!(jwk.e.as_deref(), Some("AQAB"));
+        assert!(jwk.n.is_some());
+    }
+
+    #[test]
+    fn test_deserialize_microsoft_jwk() {
+        let jwk: Jwk = serde_json::from_str(
+            r#"{
+                "kty": "RSA",
+                "use": "sig",
+                "kid": "nOo3ZDrODXEK1jKWhXslHR_KXEg",
+                "x5t": "nOo3ZDrODXEK1jKWhXslHR_KXEg",
+                "x5t#S256": "ZEzff9b6D4r2Mr5VYGYuvNeIjSU4YjnAyqjG_MhM4PM",
+                "n": "oaLLT9hkcSj2tGfZsjbu7Xz1Krs0qEicXPmEsJKOBQHauZ_kRM1HdEkgOJbUznUspE6xOuOSXjlzErqBxXAu4SCvcvVOCYG2v9G3-uIrLF5dstD0sYHBo1VomtKxzF90Vslrkn6rNQgUGIWgvuQTxm1uRklYFPEcTIRw0LnYknzJ06GC9ljKR617wABVrZNkBuDgQKj37qcyxoaxIGdxEcmVFZXJyrxDgdXh9owRmZn6LIJlGjZ9m59emfuwnBnsIQG7DirJwe9SXrLXnexRQWqyzCdkYaOqkpKrsjuxUj2-MHX31FqsdpJJsOAvYXGOYBKJRjhGrGdONVrZdUdTBQ",
+                "e": "AQAB",
+                "x5c": [
+                    "MIIDBTCCAe2gAwIBAgIQN33ROaIJ6bJBWDCxtmJEbjANBgkqhkiG9w0BAQsFADAtMSswKQYDVQQDEyJhY2NvdW50cy5hY2Nlc3Njb250cm9sLndpbmRvd3MubmV0"
+                ],
+                "issuer": "https://login.microsoftonline.com/{tenantid}/v2.0"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(jwk.kid.as_deref(), Some("nOo3ZDrODXEK1jKWhXslHR_KXEg"));
+        assert_eq!(jwk.x5t, jwk.kid);
+        assert_eq!(
+            jwk.x5t_s256.as_deref(),
+            Some("ZEzff9b6D4r2Mr5VYGYuvNeIjSU4YjnAyqjG_MhM4PM")
+        );
+        assert_eq!(jwk.x5c.as_ref().map(Vec::len), Some(1));
+    }
+
+    #[test]
+    fn test_deserialize_ec_jwk_round_trip() {
+        let json = r#"{"kty":"EC","use":"sig","key_ops":["verify"],"crv":"P-256","x":"f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU","y":"x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0","kid":"ec-1"}"#;
+        let jwk: Jwk = serde_json::from_str(json).unwrap();
+
+        assert_eq!(jwk.crv.as_deref(), Some("P-256"));
+        assert_eq!(jwk.key_ops, Some(vec!["verify".to_string()]));
+        assert!(jwk.d.is_none());
+
+        let serialized = serde_json::to_value(&jwk).unwrap();
+        assert_eq!(
+            serialized,
+            serde_json::from_str::<serde_json::Value>(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_jwk_set_find() {
+        let jwks: JwkSet = serde_json::from_str(
+            r#"{"keys": [
+                {"kty": "oct", "kid": "key-1", "k": "c2VjcmV0LTE"},
+                {"kty": "oct", "kid": "key-2", "k": "c2VjcmV0LTI"},
+                {"kty": "oct", "k": "bm8ta2lk"}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            jwks.find("key-2").unwrap().k.as_deref(),
+            Some("c2VjcmV0LTI")
+        );
+        assert!(matches!(
+            jwks.verifying_key("key-1"),
+            Ok(VerifyingKey::Secret(_))
+        ));
+
+        let err = jwks.find("key-3").unwrap_err();
+        assert!(matches!(err, Error::UnknownKeyId(ref kid) if kid == "key-3"));
+        assert!(err.to_string().contains("key-3"));
+        assert!(matches!(
+            jwks.verifying_key("key"),
+            Err(Error::UnknownKeyId(_))
+        ));
+    }
+
+    #[test]
+    fn test_jwk_set_find_by_x5t() {
+        let jwks: JwkSet = serde_json::from_str(
+            r#"{"keys": [
+                {"kty": "oct", "kid": "key-1", "x5t": "thumb-1", "k": "c2VjcmV0LTE"},
+                {"kty": "oct", "x5t": "thumb-2", "x5t#S256": "thumb-256", "k": "c2VjcmV0LTI"}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            jwks.find_by_x5t("thumb-2").unwrap().k.as_deref(),
+            Some("c2VjcmV0LTI")
+        );
+        assert!(matches!(
+            jwks.find_by_x5t("thumb-3"),
+            Err(Error::UnknownKeyId(ref x5t)) if x5t == "thumb-3"
+        ));
+
+        let header = Header {
+            x5t: Some("thumb-2".to_string()),
+            ..Header::default()
+        };
+        assert_eq!(
+            jwks.find_for_header(&header).unwrap().k.as_deref(),
+            Some("c2VjcmV0LTI")
+        );
+        assert!(matches!(
+            jwks.verifying_key_for_header(&header),
+            Ok(VerifyingKey::Secret(_))
+        ));
+
+        let header = Header {
+            x5t_s256: Some("thumb-256".to_string()),
+            ..Header::default()
+        };
+        assert_eq!(
+            jwks.find_for_header(&header).unwrap().k.as_deref(),
+            Some("c2VjcmV0LTI")
+        );
+
+        // `kid` takes precedence over the thumbprints.
+        let header = Header {
+            kid: Some("key-1".to_string()),
+            x5t: Some("thumb-2".to_string()),
+            ..Header::default()
+        };
+        assert_eq!(
+            jwks.find_for_header(&header).unwrap().k.as_deref(),
+            Some("c2VjcmV0LTE")
+        );
+
+        assert!(matches!(
+            jwks.find_for_header(&Header::default()),
+            Err(Error::MissingKeyId)
+        ));
+    }
+}
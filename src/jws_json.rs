@@ -0,0 +1,119 @@
+use serde::Deserialize;
+
+use crate::decode::{decode_payload, verify_segments};
+use crate::{DecodedJwt, Error, JwtVerifier, ValidationOptions};
+
+/// A JWS in the general JSON serialization (RFC 7515, section 7.2.1).
+#[derive(Debug, Deserialize)]
+struct GeneralJws {
+    payload: String,
+    signatures: Vec<JwsSignature>,
+}
+
+/// A JWS in the flattened JSON serialization (RFC 7515, section 7.2.2).
+#[derive(Debug, Deserialize)]
+struct FlattenedJws {
+    payload: String,
+    protected: String,
+    signature: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwsSignature {
+    protected: String,
+    signature: String,
+}
+
+/// Decodes and validates a JWS in the general JSON serialization.
+///
+/// Each entry of the `signatures` array is tried in order and the first one
+/// whose protected header is accepted by `validation_options` and whose
+/// signature verifies with `verifying_key` is used. The claims are then
+/// validated as with [`decode`](crate::decode), and their errors are
+/// returned as is.
+///
+/// # Arguments
+///
+/// * `json` - The JSON serialized JWS.
+/// * `verifying_key` - The key to be used for signature verification.
+/// * `validation_options` - The validation options for the claims within the JWS.
+///
+/// # Returns
+///
+/// Returns a `DecodedJwt` with the protected header and signature of the matching
+/// entry and the payload. If no entry matches, returns `Error::InvalidSignature`
+/// when some signature was checked and failed, or else the error of the first
+/// entry, e.g. for a malformed header or a disallowed algorithm.
+pub fn decode_json_serialization<V: JwtVerifier + ?Sized>(
+    json: &str,
+    verifying_key: &V,
+    validation_options: &ValidationOptions,
+) -> Result<DecodedJwt, Error> {
+    let jws: GeneralJws = serde_json::from_str(json)?;
+
+    let mut signature_failed = false;
+    let mut rejected = None;
+    for signature in &jws.signatures {
+        match verify_segments(
+            &signature.protected,
+            &jws.payload,
+            &signature.signature,
+            verifying_key,
+            validation_options,
+        ) {
+            Ok((header, signature)) => {
+                let payload = decode_payload(&jws.payload, validation_options)?;
+                return Ok(DecodedJwt {
+                    header,
+                    payload,
+                    signature,
+                });
+            }
+            Err(err) if err.is_invalid_signature() => signature_failed = true,
+            Err(err) => {
+                rejected.get_or_insert(err);
+            }
+        }
+    }
+
+    match rejected {
+        Some(err) if !signature_failed => Err(err),
+        _ => Err(Error::InvalidSignature),
+    }
+}
+
+/// Decodes and validates a JWS in the flattened JSON serialization, whose
+/// single signature is given by top-level `protected` and `signature` members.
+///
+/// # Arguments
+///
+/// * `json` - The JSON serialized JWS.
+/// * `verifying_key` - The key to be used for signature verification.
+/// * `validation_options` - The validation options for the claims within the JWS.
+///
+/// # Returns
+///
+/// Returns a `DecodedJwt` with the protected header, payload and signature, or
+/// an `Error` if verification or validation fails.
+pub fn decode_flattened_json<V: JwtVerifier + ?Sized>(
+    json: &str,
+    verifying_key: &V,
+    validation_options: &ValidationOptions,
+) -> Result<DecodedJwt, Error> {
+    let jws: FlattenedJws = serde_json::from_str(json)?;
+
+    let (header, signature) = verify_segments(
+        &jws.protected,
+        &jws.payload,
+        &jws.signature,
+        verifying_key,
+        validation_options,
+    )?;
+    let payload = decode_payload(&jws.payload, validation_options)?;
+
+    Ok(DecodedJwt {
+        header,
+        payload,
+        signature,
+    })
+}
@@ -18,13 +18,24 @@ pub fn wasm_encode(header: &str, key: &str, payload: &str) -> Result<String, JsV
 }
 
 mod algorithm;
+mod base64url;
+mod bearer;
+pub mod compat;
 mod decode;
 mod ecdsa_signing;
+mod eddsa_signing;
 mod encode;
 mod error;
 mod header;
 mod hmac_signing;
+mod jwk;
+mod jws_json;
+mod oidc;
 mod pem;
+#[cfg(feature = "pkcs12")]
+mod pkcs12;
+pub mod prelude;
+mod pss;
 mod rsa_signing;
 mod secret_key;
 mod signing_key;
@@ -32,15 +43,27 @@ mod validation;
 mod verifying_key;
 
 pub use algorithm::Algorithm;
+pub use base64url::{base64url_decode, base64url_encode};
+pub use bearer::extract_bearer;
 pub use decode::*;
 pub(crate) use ecdsa_signing::*;
+pub(crate) use eddsa_signing::*;
 pub use encode::*;
 pub use error::Error;
-pub use header::{Header, Jwk};
+pub use header::Header;
 pub(crate) use hmac_signing::*;
-pub(crate) use pem::*;
+pub use jwk::{Jwk, JwkSet, OtherPrimeInfo};
+pub use jws_json::*;
+pub use oidc::{verify_at_hash, verify_c_hash};
+pub use pem::{PemEncodedKey, PemType, Standard};
+#[cfg(feature = "pkcs12")]
+pub use pkcs12::from_pkcs12;
+pub use pss::{Mgf1Hash, PssVerifier};
 use rsa_signing::*;
 pub use secret_key::SecretKey;
 pub use signing_key::*;
-pub use validation::ValidationOptions;
+pub use validation::{
+    validate_claims, ClaimValidator, ExpValidation, IssuerValidator, TimeProvider,
+    ValidationBuilder, ValidationOptions, DEFAULT_LEEWAY_SECS,
+};
 pub use verifying_key::*;
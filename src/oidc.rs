@@ -0,0 +1,87 @@
+use base64::Engine;
+use serde_json::Value;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use crate::{Algorithm, Error};
+
+/// Checks the `at_hash` claim of an OpenID Connect ID token against the
+/// access token issued with it (OpenID Connect Core 1.0, section 3.2.2.9).
+///
+/// The access token is hashed with the hash of `alg`, the algorithm the ID
+/// token is signed with, and the base64url encoded left-most half of the
+/// digest must equal `at_hash`. EdDSA uses SHA-512, as for Ed25519.
+pub fn verify_at_hash(id_claims: &Value, access_token: &str, alg: Algorithm) -> Result<(), Error> {
+    verify_token_hash(id_claims, "at_hash", access_token, alg)
+}
+
+/// Checks the `c_hash` claim of an OpenID Connect ID token against the
+/// authorization code issued with it (OpenID Connect Core 1.0, section
+/// 3.3.2.11). The hash is computed like for [`verify_at_hash`].
+pub fn verify_c_hash(id_claims: &Value, code: &str, alg: Algorithm) -> Result<(), Error> {
+    verify_token_hash(id_claims, "c_hash", code, alg)
+}
+
+fn verify_token_hash(
+    id_claims: &Value,
+    claim: &str,
+    value: &str,
+    alg: Algorithm,
+) -> Result<(), Error> {
+    let expected = id_claims
+        .get(claim)
+        .ok_or_else(|| Error::MissingRequiredClaim(claim.to_string()))?
+        .as_str()
+        .ok_or_else(|| Error::InvalidClaim(format!("{} must be a string", claim)))?;
+
+    let digest = match alg.hash_len() {
+        32 => Sha256::digest(value).to_vec(),
+        48 => Sha384::digest(value).to_vec(),
+        _ => Sha512::digest(value).to_vec(),
+    };
+    let actual =
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&digest[..digest.len() / 2]);
+
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(Error::InvalidClaim(format!("{} mismatch", claim)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_verify_at_hash() {
+        // Example from OpenID Connect Core 1.0, appendix A.
+        let access_token = "jHkWEdUXMU1BwAsC4vtUsZwnNvTIxEl0z9K3vx5KF0Y";
+        let id_claims = json!({ "at_hash": "77QmUPtjPfzWtF2AnpK9RQ" });
+
+        assert!(verify_at_hash(&id_claims, access_token, Algorithm::RS256).is_ok());
+        assert!(verify_at_hash(&id_claims, access_token, Algorithm::ES256).is_ok());
+        assert!(matches!(
+            verify_at_hash(&id_claims, access_token, Algorithm::RS384),
+            Err(Error::InvalidClaim(_))
+        ));
+        assert!(matches!(
+            verify_at_hash(&id_claims, "another-token", Algorithm::RS256),
+            Err(Error::InvalidClaim(_))
+        ));
+        assert!(matches!(
+            verify_at_hash(&json!({}), access_token, Algorithm::RS256),
+            Err(Error::MissingRequiredClaim(ref claim)) if claim == "at_hash"
+        ));
+    }
+
+    #[test]
+    fn test_verify_c_hash() {
+        // Example from OpenID Connect Core 1.0, appendix A.
+        let code = "Qcb0Orv1zh30vL1MPRsbm-diHiMwcLyZvn1arpZv-Jxf_11jnpEX3Tgfvk";
+        let id_claims = json!({ "c_hash": "LDktKdoQak3Pk0cnXxCltA" });
+
+        assert!(verify_c_hash(&id_claims, code, Algorithm::RS256).is_ok());
+        assert!(verify_c_hash(&id_claims, "another-code", Algorithm::RS256).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+use p12_keystore::{KeyStore, Pkcs12ImportPolicy};
+
+use crate::pem::certificate_public_key;
+use crate::{Error, PemEncodedKey, PemType, SigningKey, VerifyingKey};
+
+/// Extracts the private key and the public key of the leaf certificate from
+/// a DER encoded PKCS#12 (`.p12`/`.pfx`) bundle.
+fn keys_from_pkcs12(der: &[u8], password: &str) -> Result<(PemEncodedKey, PemEncodedKey), Error> {
+    let keystore = KeyStore::from_pkcs12(der, password, Pkcs12ImportPolicy::Strict)?;
+    let (_, chain) = keystore
+        .private_key_chain()
+        .ok_or(Error::InvalidKeyFormat)?;
+    let leaf = chain.certs().first().ok_or(Error::InvalidKeyFormat)?;
+
+    let private_key = pem::encode(&pem::Pem::new("PRIVATE KEY", chain.key().as_der()));
+    let public_key = pem::encode(&pem::Pem::new(
+        "PUBLIC KEY",
+        certificate_public_key(leaf.as_der())?,
+    ));
+
+    Ok((
+        PemEncodedKey::new(private_key.as_bytes())?,
+        PemEncodedKey::new(public_key.as_bytes())?,
+    ))
+}
+
+/// Reads the private key and leaf certificate of a DER encoded PKCS#12
+/// (`.p12`/`.pfx`) bundle protected by `password`, returning the matching
+/// signing and verifying keys.
+pub fn from_pkcs12(der: &[u8], password: &str) -> Result<(SigningKey, VerifyingKey), Error> {
+    let (private_key, public_key) = keys_from_pkcs12(der, password)?;
+
+    let signing_key = match private_key.pem_type {
+        PemType::RsaPrivate => SigningKey::RsaKey(private_key),
+        PemType::EcPrivate => SigningKey::EcKey(private_key),
+        PemType::EdPrivate => SigningKey::EdKey(private_key),
+        _ => return Err(Error::InvalidKeyFormat),
+    };
+    let verifying_key = match public_key.pem_type {
+        PemType::RsaPublic => VerifyingKey::RsaKey(public_key),
+        PemType::EcPublic => VerifyingKey::EcKey(public_key),
+        PemType::EdPublic => VerifyingKey::EdKey(public_key),
+        _ => return Err(Error::InvalidKeyFormat),
+    };
+
+    Ok((signing_key, verifying_key))
+}
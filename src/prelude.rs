@@ -0,0 +1,19 @@
+//! The everyday API in a single import.
+//!
+//! ```
+//! use jwt_rustcrypto::prelude::*;
+//! use serde_json::json;
+//!
+//! let header = Header::new(Algorithm::HS256);
+//! let signing_key = SigningKey::from_secret(b"secret");
+//! let token = encode(&header, &signing_key, &json!({ "sub": "1234567890" })).unwrap();
+//!
+//! let verifying_key = VerifyingKey::from_secret(b"secret");
+//! let validation_options = ValidationOptions::new(Algorithm::HS256).without_expiry();
+//! let decoded: DecodedJwt = decode(&token, &verifying_key, &validation_options).unwrap();
+//! assert_eq!(decoded.payload["sub"], "1234567890");
+//! ```
+pub use crate::{
+    decode, decode_only, encode, Algorithm, DecodedJwt, Error, Header, SigningKey,
+    ValidationOptions, VerifyingKey,
+};
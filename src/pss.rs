@@ -0,0 +1,162 @@
+use rsa::traits::PublicKeyParts;
+use rsa::{BigUint, RsaPublicKey};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use crate::{Algorithm, Error, JwtVerifier, VerifyingKey};
+
+type DigestFn = fn(&[u8]) -> Vec<u8>;
+type MaskFn = fn(&[u8], usize) -> Vec<u8>;
+
+/// Hash function used by the MGF1 mask generation function of RSA-PSS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mgf1Hash {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+/// Verifies `PS256`, `PS384` and `PS512` signatures with a configurable MGF1
+/// hash.
+///
+/// JWA (RFC 7518, section 3.5) requires the MGF1 hash to match the message
+/// digest, which is what [`VerifyingKey`] and this verifier do by default.
+/// RFC 8017 allows them to differ, so [`with_mgf1_hash`](Self::with_mgf1_hash)
+/// can select another MGF1 hash to interoperate with signers that require it.
+/// The salt length is always the digest length.
+#[derive(Debug, Clone)]
+pub struct PssVerifier {
+    key: RsaPublicKey,
+    mgf1_hash: Option<Mgf1Hash>,
+}
+
+impl PssVerifier {
+    /// Creates a verifier for an RSA verifying key, using the message digest
+    /// as MGF1 hash.
+    pub fn new(verifying_key: &VerifyingKey) -> Result<Self, Error> {
+        match verifying_key {
+            VerifyingKey::RsaKey(rsa_key) => Ok(Self {
+                key: rsa_key.as_rsa_public_key()?,
+                mgf1_hash: None,
+            }),
+            _ => Err(Error::InvalidKeyFormat),
+        }
+    }
+
+    /// Uses `mgf1_hash` for MGF1 regardless of the message digest.
+    pub fn with_mgf1_hash(self, mgf1_hash: Mgf1Hash) -> Self {
+        Self {
+            mgf1_hash: Some(mgf1_hash),
+            ..self
+        }
+    }
+}
+
+impl JwtVerifier for PssVerifier {
+    fn verify(&self, alg: &Algorithm, signing_input: &str, signature: &[u8]) -> Result<(), Error> {
+        let (digest, default_mgf1_hash): (DigestFn, _) = match alg {
+            Algorithm::PS256 => (|data| Sha256::digest(data).to_vec(), Mgf1Hash::Sha256),
+            Algorithm::PS384 => (|data| Sha384::digest(data).to_vec(), Mgf1Hash::Sha384),
+            Algorithm::PS512 => (|data| Sha512::digest(data).to_vec(), Mgf1Hash::Sha512),
+            _ => return Err(Error::UnsupportedAlgorithm),
+        };
+        let mgf1: MaskFn = match self.mgf1_hash.unwrap_or(default_mgf1_hash) {
+            Mgf1Hash::Sha256 => mgf1::<Sha256>,
+            Mgf1Hash::Sha384 => mgf1::<Sha384>,
+            Mgf1Hash::Sha512 => mgf1::<Sha512>,
+        };
+
+        let encoded_message = rsa_verification_primitive(&self.key, signature)?;
+        emsa_pss_verify(
+            &digest(signing_input.as_bytes()),
+            &encoded_message,
+            self.key.n().bits() - 1,
+            digest,
+            mgf1,
+        )
+    }
+}
+
+/// RSAVP1 (RFC 8017, section 5.2.2), returning the encoded message of
+/// `ceil((modBits - 1) / 8)` bytes.
+fn rsa_verification_primitive(key: &RsaPublicKey, signature: &[u8]) -> Result<Vec<u8>, Error> {
+    if signature.len() != key.size() {
+        return Err(Error::InvalidSignature);
+    }
+
+    let s = BigUint::from_bytes_be(signature);
+    if &s >= key.n() {
+        return Err(Error::InvalidSignature);
+    }
+
+    let message = s.modpow(key.e(), key.n()).to_bytes_be();
+    let encoded_len = (key.n().bits() - 1).div_ceil(8);
+    if message.len() > encoded_len {
+        return Err(Error::InvalidSignature);
+    }
+
+    let mut encoded_message = vec![0; encoded_len - message.len()];
+    encoded_message.extend_from_slice(&message);
+    Ok(encoded_message)
+}
+
+/// EMSA-PSS-VERIFY (RFC 8017, section 9.1.2) with a salt as long as the
+/// digest.
+fn emsa_pss_verify(
+    message_hash: &[u8],
+    encoded_message: &[u8],
+    encoded_bits: usize,
+    digest: DigestFn,
+    mgf1: MaskFn,
+) -> Result<(), Error> {
+    let hash_len = message_hash.len();
+    let salt_len = hash_len;
+    let encoded_len = encoded_message.len();
+    if encoded_len < hash_len + salt_len + 2 || encoded_message[encoded_len - 1] != 0xbc {
+        return Err(Error::InvalidSignature);
+    }
+
+    let (masked_db, hash) = encoded_message[..encoded_len - 1].split_at(encoded_len - hash_len - 1);
+    let unused_bits = 8 * encoded_len - encoded_bits;
+    let top_bits_mask = 0xffu8 >> unused_bits;
+    if masked_db[0] & !top_bits_mask != 0 {
+        return Err(Error::InvalidSignature);
+    }
+
+    let mut db: Vec<u8> = masked_db
+        .iter()
+        .zip(mgf1(hash, masked_db.len()))
+        .map(|(masked, mask)| masked ^ mask)
+        .collect();
+    db[0] &= top_bits_mask;
+
+    let padding_len = encoded_len - hash_len - salt_len - 2;
+    if db[..padding_len].iter().any(|byte| *byte != 0) || db[padding_len] != 0x01 {
+        return Err(Error::InvalidSignature);
+    }
+
+    let salt = &db[db.len() - salt_len..];
+    let mut prefixed = vec![0u8; 8];
+    prefixed.extend_from_slice(message_hash);
+    prefixed.extend_from_slice(salt);
+
+    if digest(&prefixed) == hash {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature)
+    }
+}
+
+/// MGF1 (RFC 8017, appendix B.2.1).
+fn mgf1<D: Digest>(seed: &[u8], mask_len: usize) -> Vec<u8> {
+    let mut mask = Vec::with_capacity(mask_len);
+    let mut counter = 0u32;
+    while mask.len() < mask_len {
+        let mut hasher = D::new();
+        hasher.update(seed);
+        hasher.update(counter.to_be_bytes());
+        mask.extend_from_slice(&hasher.finalize());
+        counter += 1;
+    }
+    mask.truncate(mask_len);
+    mask
+}
@@ -1,7 +1,5 @@
-use crate::{Algorithm, Error, PemType, SigningKey, Standard as PemStandard};
-use base64::Engine;
-use rsa::pkcs1::DecodeRsaPrivateKey;
-use rsa::pkcs8::DecodePrivateKey;
+use crate::{Algorithm, Error, SigningKey};
+use rand::{CryptoRng, RngCore};
 use rsa::signature::RandomizedSigner;
 use rsa::signature::SignatureEncoding;
 use rsa::{pkcs1v15::SigningKey as Pkcs1SigningKey, pss::SigningKey as PssSigningKey};
@@ -18,15 +16,14 @@ enum SigningSchema {
 }
 
 impl SigningSchema {
-    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
-        let mut rng = rand::thread_rng();
+    fn sign<R: CryptoRng + RngCore>(&self, data: &[u8], rng: &mut R) -> Result<Vec<u8>, Error> {
         let signature_bytes = match self {
-            SigningSchema::Pkcs1Sha256(signer) => signer.sign_with_rng(&mut rng, data).to_vec(),
-            SigningSchema::Pkcs1Sha384(signer) => signer.sign_with_rng(&mut rng, data).to_vec(),
-            SigningSchema::Pkcs1Sha512(signer) => signer.sign_with_rng(&mut rng, data).to_vec(),
-            SigningSchema::PssSha256(signer) => signer.sign_with_rng(&mut rng, data).to_vec(),
-            SigningSchema::PssSha384(signer) => signer.sign_with_rng(&mut rng, data).to_vec(),
-            SigningSchema::PssSha512(signer) => signer.sign_with_rng(&mut rng, data).to_vec(),
+            SigningSchema::Pkcs1Sha256(signer) => signer.sign_with_rng(rng, data).to_vec(),
+            SigningSchema::Pkcs1Sha384(signer) => signer.sign_with_rng(rng, data).to_vec(),
+            SigningSchema::Pkcs1Sha512(signer) => signer.sign_with_rng(rng, data).to_vec(),
+            SigningSchema::PssSha256(signer) => signer.sign_with_rng(rng, data).to_vec(),
+            SigningSchema::PssSha384(signer) => signer.sign_with_rng(rng, data).to_vec(),
+            SigningSchema::PssSha512(signer) => signer.sign_with_rng(rng, data).to_vec(),
         };
 
         Ok(signature_bytes)
@@ -42,14 +39,7 @@ fn create_signing_scheme(
         _ => return Err(Error::UnsupportedAlgorithm),
     };
 
-    if rsa_key.pem_type != PemType::RsaPrivate {
-        return Err(Error::InvalidRsaKeyType("Expected RsaPrivate".to_string()));
-    }
-
-    let rsa_key = match rsa_key.standard {
-        PemStandard::Pkcs1 => rsa::RsaPrivateKey::from_pkcs1_pem(&pem::encode(&rsa_key.content))?,
-        PemStandard::Pkcs8 => rsa::RsaPrivateKey::from_pkcs8_pem(&pem::encode(&rsa_key.content))?,
-    };
+    let rsa_key = rsa_key.as_rsa_private_key()?;
 
     let signing_schema = match alg {
         Algorithm::RS256 => SigningSchema::Pkcs1Sha256(Pkcs1SigningKey::<Sha256>::new(rsa_key)),
@@ -68,11 +58,20 @@ pub(crate) fn sign_rsa(
     data: &str,
     signing_key: &SigningKey,
     alg: &Algorithm,
-) -> Result<String, Error> {
+) -> Result<Vec<u8>, Error> {
+    sign_rsa_with_rng(data, signing_key, alg, &mut rand::thread_rng())
+}
+
+pub(crate) fn sign_rsa_with_rng<R: CryptoRng + RngCore>(
+    data: &str,
+    signing_key: &SigningKey,
+    alg: &Algorithm,
+    rng: &mut R,
+) -> Result<Vec<u8>, Error> {
     let signing_schema = create_signing_scheme(alg, signing_key)?;
-    let signature = signing_schema.sign(data.as_bytes())?;
+    let signature = signing_schema.sign(data.as_bytes(), rng)?;
 
-    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(signature))
+    Ok(signature)
 }
 
 #[cfg(test)]
@@ -95,11 +94,8 @@ mod tests {
         let alg = Algorithm::RS256;
 
         let signing_scheme = create_signing_scheme(&alg, &signing_key);
-        if signing_scheme.is_err() {
-            println!(
-                "Error: {:?}",
-                signing_scheme.as_ref().unwrap_err().to_string()
-            );
+        if let Err(err) = &signing_scheme {
+            println!("Error: {:?}", err.to_string());
         }
         assert!(signing_scheme.is_ok());
     }
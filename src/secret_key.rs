@@ -1,14 +1,26 @@
-#[derive(Debug, Clone)]
+use std::sync::Arc;
+
+/// An HMAC secret. The secret is shared, so clones are cheap.
+#[derive(Clone)]
 pub struct SecretKey {
-    content: Vec<u8>,
+    content: Arc<[u8]>,
 }
 
 impl SecretKey {
     pub fn new(content: Vec<u8>) -> Self {
-        Self { content }
+        Self {
+            content: content.into(),
+        }
     }
 
     pub fn inner(&self) -> &[u8] {
         &self.content
     }
 }
+
+/// Redacts the secret so that logging a key never exposes it.
+impl std::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretKey").finish_non_exhaustive()
+    }
+}
@@ -1,6 +1,21 @@
-use crate::{Error, PemEncodedKey, SecretKey};
+use crate::{Algorithm, Error, Jwk, PemEncodedKey, SecretKey};
 use base64::Engine;
 
+/// Signs JWTs, allowing `encode` to be used with key backends other than
+/// [`SigningKey`], such as an HSM or a remote signing service.
+pub trait JwtSigner {
+    /// Signs `signing_input` (the encoded header and payload segments joined
+    /// by `.`) with the algorithm `alg`, returning the raw signature bytes.
+    /// ECDSA signatures must use the fixed-width `r || s` form.
+    fn sign(&self, alg: &Algorithm, signing_input: &str) -> Result<Vec<u8>, Error>;
+
+    /// The only algorithm this signer supports, if it is bound to one.
+    /// `encode` rejects headers declaring a different algorithm.
+    fn algorithm(&self) -> Option<Algorithm> {
+        None
+    }
+}
+
 /// A signing key used to sign JWTs
 #[derive(Debug, Clone)]
 pub enum SigningKey {
@@ -36,6 +51,71 @@ impl SigningKey {
         Ok(Self::EdKey(key))
     }
 
+    /// Builds an EdDSA key from a raw 32-byte Ed25519 seed.
+    pub fn from_ed25519_seed(seed: &[u8; 32]) -> Result<Self, Error> {
+        let key = PemEncodedKey::from_ed25519_seed(seed)?;
+        Ok(Self::EdKey(key))
+    }
+
+    /// Builds a signing key from a private JWK. `EC` keys with a `d` member
+    /// and `oct` keys are supported. Keys declared for encryption are rejected
+    /// with [`Error::KeyNotForSigning`].
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        let decode_member = |member: &Option<String>| -> Result<Vec<u8>, Error> {
+            let value = member.as_deref().ok_or(Error::InvalidKeyFormat)?;
+            Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(value)?)
+        };
+
+        jwk.check_signature_use()?;
+        match jwk.kty.as_str() {
+            "EC" => {
+                let crv = jwk.crv.as_deref().ok_or(Error::InvalidKeyFormat)?;
+                let key = PemEncodedKey::from_ec_private_scalar(crv, &decode_member(&jwk.d)?)?;
+                Ok(Self::EcKey(key))
+            }
+            "oct" => Ok(Self::from_secret(&decode_member(&jwk.k)?)),
+            kty => Err(Error::UnsupportedKeyType(kty.to_string())),
+        }
+    }
+
+    /// Exports the key as a private JWK, including the `d` member of EC keys.
+    /// `EC` and `oct` keys are supported.
+    pub fn to_jwk(&self) -> Result<Jwk, Error> {
+        let encode = |bytes: &[u8]| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+
+        match self {
+            Self::Secret(secret) => Ok(Jwk {
+                kty: "oct".to_string(),
+                k: Some(encode(secret.inner())),
+                ..Jwk::default()
+            }),
+            Self::EcKey(key) => {
+                let (crv, point) = key.public_from_private()?.ec_public_point()?;
+                let (x, y) = point[1..].split_at((point.len() - 1) / 2);
+                Ok(Jwk {
+                    kty: "EC".to_string(),
+                    crv: Some(crv.to_string()),
+                    x: Some(encode(x)),
+                    y: Some(encode(y)),
+                    d: Some(encode(&key.ec_private_scalar()?)),
+                    ..Jwk::default()
+                })
+            }
+            Self::RsaKey(_) => Err(Error::UnsupportedKeyType("RSA".to_string())),
+            Self::EdKey(_) => Err(Error::UnsupportedKeyType("OKP".to_string())),
+        }
+    }
+
+    /// The kind of key, as reported in `Error::KeyAlgorithmMismatch`.
+    pub(crate) fn key_kind(&self) -> &'static str {
+        match self {
+            Self::Secret(_) => "secret",
+            Self::RsaKey(_) => "RSA",
+            Self::EcKey(_) => "EC",
+            Self::EdKey(_) => "Ed25519",
+        }
+    }
+
     pub fn from_rsa_der(key: &[u8]) -> Result<Self, Error> {
         let key = PemEncodedKey::new(key)?;
         Ok(Self::RsaKey(key))
@@ -1,9 +1,14 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use base64::Engine;
 use serde_json::map::Map;
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 
 use crate::Algorithm;
 use crate::Error;
@@ -26,27 +31,187 @@ extern "C" {
     fn now() -> f64;
 }
 
+/// Leeway (in seconds) applied to time-related claims by default, unless
+/// [`ValidationOptions::set_default_leeway`] sets another one.
+pub const DEFAULT_LEEWAY_SECS: u64 = 0;
+
+static DEFAULT_LEEWAY: OnceLock<u64> = OnceLock::new();
+
+fn default_leeway() -> u64 {
+    DEFAULT_LEEWAY.get().copied().unwrap_or(DEFAULT_LEEWAY_SECS)
+}
+
+/// A source of the current time, in seconds, used in place of the system
+/// clock when validating time-related claims.
+#[derive(Clone)]
+pub struct TimeProvider(Arc<dyn Fn() -> u64 + Send + Sync>);
+
+impl TimeProvider {
+    pub fn new<F: Fn() -> u64 + Send + Sync + 'static>(provider: F) -> Self {
+        Self(Arc::new(provider))
+    }
+
+    /// Returns the current time reported by the provider.
+    pub fn now(&self) -> u64 {
+        (self.0)()
+    }
+}
+
+impl fmt::Debug for TimeProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TimeProvider(<fn>)")
+    }
+}
+
+impl PartialEq for TimeProvider {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// A predicate deciding whether an `iss` claim is acceptable, for issuers
+/// that can't be matched exactly, e.g. by prefix or pattern.
+#[derive(Clone)]
+pub struct IssuerValidator(Arc<dyn Fn(&str) -> bool + Send + Sync>);
+
+impl IssuerValidator {
+    pub fn new<F: Fn(&str) -> bool + Send + Sync + 'static>(validator: F) -> Self {
+        Self(Arc::new(validator))
+    }
+
+    /// Returns `true` if `issuer` is accepted.
+    pub fn accepts(&self, issuer: &str) -> bool {
+        (self.0)(issuer)
+    }
+}
+
+impl fmt::Debug for IssuerValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("IssuerValidator(<fn>)")
+    }
+}
+
+impl PartialEq for IssuerValidator {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// A business rule run on the claims after the standard checks, e.g. that
+/// `scope` contains `admin`. Rejections are usually reported with
+/// [`Error::ClaimValidation`].
+#[derive(Clone)]
+pub struct ClaimValidator(Arc<ClaimValidatorFn>);
+
+type ClaimValidatorFn = dyn Fn(&Value) -> Result<(), Error> + Send + Sync;
+
+impl ClaimValidator {
+    pub fn new<F: Fn(&Value) -> Result<(), Error> + Send + Sync + 'static>(validator: F) -> Self {
+        Self(Arc::new(validator))
+    }
+
+    /// Runs the validator on the claims of a token.
+    pub fn validate(&self, claims: &Value) -> Result<(), Error> {
+        (self.0)(claims)
+    }
+}
+
+impl fmt::Debug for ClaimValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ClaimValidator(<fn>)")
+    }
+}
+
+impl PartialEq for ClaimValidator {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// How the expiration time (`exp` claim) is validated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExpValidation {
+    /// `exp` must be present and not in the past.
+    #[default]
+    Required,
+    /// `exp` is checked when present, tokens without it are accepted.
+    OptionalIfPresent,
+    /// `exp` is not checked at all.
+    Ignore,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ValidationOptions {
     /// General leeway (in seconds) applied to all time-related claims like `exp`, `nbf`, and `iat`.
     pub leeway: u64,
-    /// Validate the expiration time (`exp` claim).
-    pub validate_exp: bool,
-    /// Validate the not-before time (`nbf` claim).
+    /// How the expiration time (`exp` claim) is validated.
+    pub exp_validation: ExpValidation,
+    /// Validate the not-before time (`nbf` claim), which must be present.
     pub validate_nbf: bool,
+    /// Validate the not-before time (`nbf` claim) when present, accepting
+    /// tokens without it.
+    pub validate_nbf_if_present: bool,
+    /// Maximum age of the token according to its `iat` claim, which must be
+    /// present when set.
+    pub max_age: Option<Duration>,
     /// Set of acceptable audience members.
     pub audiences: Option<HashSet<String>>,
+    /// Set of audience members that must all be present.
+    pub required_audiences: Option<HashSet<String>>,
+    /// Compare audience members ignoring ASCII case.
+    pub audience_case_insensitive: bool,
+    /// Validate the audience (`aud` claim) against `audiences` and
+    /// `required_audiences`. When `false`, both are ignored.
+    pub validate_aud: bool,
     /// Expected issuer.
     pub issuer: Option<String>,
+    /// Predicate the issuer must satisfy. Takes precedence over `issuer`.
+    pub issuer_validator: Option<IssuerValidator>,
     /// Expected subject.
     pub subject: Option<String>,
     /// Allowed signing algorithms for the JWT.
     pub algorithms: HashSet<Algorithm>,
     /// Required claims.
     pub required_claims: Option<HashSet<String>>,
+    /// Accepted values of the `typ` header parameter.
+    pub accepted_typ: Option<HashSet<String>>,
+    /// Accept ECDSA signatures encoded as ASN.1 DER instead of the fixed-width
+    /// `r || s` form required by RFC 7518.
+    pub accept_der_ecdsa_signatures: bool,
+    /// Source of the current time. The system clock is used when unset.
+    pub time_provider: Option<TimeProvider>,
+    /// Claim validation rules to use instead of these options for tokens
+    /// whose `iss` claim matches the key.
+    pub issuer_profiles: HashMap<String, ValidationOptions>,
+    /// Accept time claims encoded as numeric strings, e.g. `"1700000000"`.
+    pub lenient_numeric_claims: bool,
+    /// Accept `alg` header values that only differ in ASCII case from a
+    /// supported algorithm, e.g. `rs256`.
+    pub case_insensitive_alg: bool,
+    /// Reject tokens whose header carries parameters this crate doesn't
+    /// know, i.e. anything that ends up in [`Header::extras`].
+    pub reject_unknown_headers: bool,
+    /// Accept base64url segments with `=` padding, which RFC 7515 forbids.
+    pub lenient_base64: bool,
+    /// Custom claim validators, run in order after the standard checks.
+    pub custom_validators: Vec<ClaimValidator>,
 }
 
 impl ValidationOptions {
+    /// Start building `ValidationOptions` with a [`ValidationBuilder`].
+    pub fn builder() -> ValidationBuilder {
+        ValidationBuilder::new()
+    }
+
+    /// Sets the leeway of every `ValidationOptions` created afterwards by
+    /// [`Default`], [`ValidationBuilder::new`] or
+    /// [`with_default_leeway`](Self::with_default_leeway), e.g. once at
+    /// startup for a consistent leeway across a codebase. It can only be set
+    /// once per process; returns `false` if it was already set.
+    pub fn set_default_leeway(leeway: u64) -> bool {
+        DEFAULT_LEEWAY.set(leeway).is_ok()
+    }
+
     /// Create a new set of `ValidationOptions` with a specific algorithm.
     pub fn new(alg: Algorithm) -> Self {
         Self {
@@ -55,14 +220,45 @@ impl ValidationOptions {
         }
     }
 
+    /// Only validate the time claims: `exp` is required and `nbf` is checked
+    /// when present, both with `leeway` seconds of leeway, while the issuer,
+    /// audience and subject are not checked. Useful when the signer is fully
+    /// trusted.
+    pub fn only_timestamps(leeway: u64) -> Self {
+        Self {
+            leeway,
+            exp_validation: ExpValidation::Required,
+            validate_nbf_if_present: true,
+            validate_aud: false,
+            ..Self::default()
+        }
+    }
+
     /// Disable expiration (`exp`) validation.
     pub fn without_expiry(self) -> Self {
         Self {
-            validate_exp: false,
+            exp_validation: ExpValidation::Ignore,
             ..Self::default()
         }
     }
 
+    /// Set how the expiration time (`exp` claim) is validated.
+    pub fn with_exp_validation(self, exp_validation: ExpValidation) -> Self {
+        Self {
+            exp_validation,
+            ..self
+        }
+    }
+
+    /// Reject tokens issued (`iat` claim) more than `max_age` ago, regardless
+    /// of their `exp` claim. Tokens without `iat` are rejected.
+    pub fn with_max_age(self, max_age: Duration) -> Self {
+        Self {
+            max_age: Some(max_age),
+            ..self
+        }
+    }
+
     /// Set acceptable audience members as a HashSet of strings.
     pub fn with_audiences<T: ToString>(self, audiences: &[T]) -> Self {
         Self {
@@ -79,6 +275,32 @@ impl ValidationOptions {
         }
     }
 
+    /// Set audience members that must all be present in the `aud` claim.
+    pub fn with_required_audiences<T: ToString>(self, audiences: &[T]) -> Self {
+        Self {
+            required_audiences: Some(audiences.iter().map(ToString::to_string).collect()),
+            ..self
+        }
+    }
+
+    /// Enable or disable audience (`aud`) validation. Enabled by default;
+    /// disabling it skips the check even when audiences are configured.
+    pub fn with_validate_aud(self, validate_aud: bool) -> Self {
+        Self {
+            validate_aud,
+            ..self
+        }
+    }
+
+    /// Compare audience members ignoring ASCII case. Audiences are compared
+    /// case-sensitively by default.
+    pub fn with_audience_case_insensitive(self, audience_case_insensitive: bool) -> Self {
+        Self {
+            audience_case_insensitive,
+            ..self
+        }
+    }
+
     /// Set the issuer claim to validate.
     pub fn with_issuer<T: ToString>(self, issuer: T) -> Self {
         Self {
@@ -87,6 +309,18 @@ impl ValidationOptions {
         }
     }
 
+    /// Accept any issuer for which `validator` returns `true`. It takes
+    /// precedence over the issuer set with [`with_issuer`](Self::with_issuer).
+    pub fn with_issuer_validator<F: Fn(&str) -> bool + Send + Sync + 'static>(
+        self,
+        validator: F,
+    ) -> Self {
+        Self {
+            issuer_validator: Some(IssuerValidator::new(validator)),
+            ..self
+        }
+    }
+
     /// Set the subject claim to validate.
     pub fn with_subject<T: ToString>(self, subject: T) -> Self {
         Self {
@@ -100,6 +334,12 @@ impl ValidationOptions {
         Self { leeway, ..self }
     }
 
+    /// Reset the leeway to the process-wide default, see
+    /// [`set_default_leeway`](Self::set_default_leeway).
+    pub fn with_default_leeway(self) -> Self {
+        self.with_leeway(default_leeway())
+    }
+
     /// Add an allowed signing algorithm.
     pub fn with_algorithm(mut self, alg: Algorithm) -> Self {
         self.algorithms.insert(alg);
@@ -115,19 +355,310 @@ impl ValidationOptions {
         }
         self
     }
+
+    /// Require every claim in `claims` to be present, whatever its value.
+    /// Custom claims such as `tenant_id` can be required like registered
+    /// ones.
+    pub fn with_required_claims<T: ToString>(self, claims: &[T]) -> Self {
+        claims.iter().fold(self, |options, claim| {
+            options.with_required_claim(claim.to_string())
+        })
+    }
+
+    /// Set the accepted values of the `typ` header, e.g. `["at+jwt"]` for
+    /// RFC 9068 access tokens. Values are compared ignoring case and an
+    /// optional `application/` prefix.
+    pub fn with_accepted_typ<T: ToString>(self, typ: &[T]) -> Self {
+        Self {
+            accepted_typ: Some(typ.iter().map(ToString::to_string).collect()),
+            ..self
+        }
+    }
+
+    /// Accept ECDSA signatures encoded as ASN.1 DER, as emitted by some
+    /// non-compliant producers. Disabled by default.
+    pub fn with_der_ecdsa_signatures(self, accept_der_ecdsa_signatures: bool) -> Self {
+        Self {
+            accept_der_ecdsa_signatures,
+            ..self
+        }
+    }
+
+    /// Use `provider` instead of the system clock as the current time for
+    /// all time comparisons. It is called once per validation.
+    pub fn with_time_provider<F: Fn() -> u64 + Send + Sync + 'static>(self, provider: F) -> Self {
+        Self {
+            time_provider: Some(TimeProvider::new(provider)),
+            ..self
+        }
+    }
+
+    /// Validate the claims of tokens issued by `issuer` with `profile`
+    /// instead of these options. The header checks, such as the allowed
+    /// algorithms, are still taken from these options.
+    pub fn with_issuer_profile<T: ToString>(
+        mut self,
+        issuer: T,
+        profile: ValidationOptions,
+    ) -> Self {
+        self.issuer_profiles.insert(issuer.to_string(), profile);
+        self
+    }
+
+    /// Accept time claims (`exp`, `nbf`, `iat`) encoded as numeric strings.
+    /// Time claims must be JSON numbers by default.
+    pub fn with_lenient_numeric_claims(self, lenient_numeric_claims: bool) -> Self {
+        Self {
+            lenient_numeric_claims,
+            ..self
+        }
+    }
+
+    /// Accept `alg` header values such as `rs256` that only differ in ASCII
+    /// case from a supported algorithm. Matching is strict by default.
+    pub fn with_case_insensitive_alg(self, case_insensitive_alg: bool) -> Self {
+        Self {
+            case_insensitive_alg,
+            ..self
+        }
+    }
+
+    /// Reject tokens carrying header parameters this crate doesn't know,
+    /// for strict JOSE processing. Disabled by default.
+    pub fn with_reject_unknown_headers(self, reject_unknown_headers: bool) -> Self {
+        Self {
+            reject_unknown_headers,
+            ..self
+        }
+    }
+
+    /// Accept token segments encoded as base64url with `=` padding, as
+    /// emitted by some non-compliant producers. Disabled by default.
+    pub fn with_lenient_base64(self, lenient_base64: bool) -> Self {
+        Self {
+            lenient_base64,
+            ..self
+        }
+    }
+
+    /// Run `validator` on the claims after the standard checks pass. Several
+    /// validators can be added and run in the order they were added.
+    pub fn with_custom_validator<F: Fn(&Value) -> Result<(), Error> + Send + Sync + 'static>(
+        mut self,
+        validator: F,
+    ) -> Self {
+        self.custom_validators.push(ClaimValidator::new(validator));
+        self
+    }
+
+    /// Returns the current time from the time provider, falling back to the
+    /// system clock.
+    pub(crate) fn now(&self) -> u64 {
+        self.time_provider
+            .as_ref()
+            .map_or_else(current_timestamp, TimeProvider::now)
+    }
+}
+
+/// Builds [`ValidationOptions`] in a single expression.
+///
+/// Unlike the `with_*` methods, list setters replace any previously
+/// configured values, so the result doesn't depend on call order.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationBuilder {
+    options: ValidationOptions,
+}
+
+impl ValidationBuilder {
+    /// Create a builder starting from the default `ValidationOptions`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the allowed signing algorithms.
+    pub fn algorithms(mut self, algorithms: &[Algorithm]) -> Self {
+        self.options.algorithms = algorithms.iter().copied().collect();
+        self
+    }
+
+    /// Set the acceptable audience members.
+    pub fn audiences<T: ToString>(mut self, audiences: &[T]) -> Self {
+        self.options.audiences = Some(audiences.iter().map(ToString::to_string).collect());
+        self
+    }
+
+    /// Set audience members that must all be present in the `aud` claim.
+    pub fn required_audiences<T: ToString>(mut self, audiences: &[T]) -> Self {
+        self.options.required_audiences = Some(audiences.iter().map(ToString::to_string).collect());
+        self
+    }
+
+    /// Enable or disable audience (`aud`) validation.
+    pub fn validate_aud(mut self, validate_aud: bool) -> Self {
+        self.options.validate_aud = validate_aud;
+        self
+    }
+
+    /// Compare audience members ignoring ASCII case.
+    pub fn audience_case_insensitive(mut self, audience_case_insensitive: bool) -> Self {
+        self.options.audience_case_insensitive = audience_case_insensitive;
+        self
+    }
+
+    /// Set the expected issuer.
+    pub fn issuer<T: ToString>(mut self, issuer: T) -> Self {
+        self.options.issuer = Some(issuer.to_string());
+        self
+    }
+
+    /// Accept any issuer for which `validator` returns `true`.
+    pub fn issuer_validator<F: Fn(&str) -> bool + Send + Sync + 'static>(
+        mut self,
+        validator: F,
+    ) -> Self {
+        self.options.issuer_validator = Some(IssuerValidator::new(validator));
+        self
+    }
+
+    /// Set the expected subject.
+    pub fn subject<T: ToString>(mut self, subject: T) -> Self {
+        self.options.subject = Some(subject.to_string());
+        self
+    }
+
+    /// Set leeway for time-related claims.
+    pub fn leeway(mut self, leeway: u64) -> Self {
+        self.options.leeway = leeway;
+        self
+    }
+
+    /// Require and validate the expiration time (`exp` claim), or ignore it.
+    pub fn validate_exp(mut self, validate_exp: bool) -> Self {
+        self.options.exp_validation = if validate_exp {
+            ExpValidation::Required
+        } else {
+            ExpValidation::Ignore
+        };
+        self
+    }
+
+    /// Set how the expiration time (`exp` claim) is validated.
+    pub fn exp_validation(mut self, exp_validation: ExpValidation) -> Self {
+        self.options.exp_validation = exp_validation;
+        self
+    }
+
+    /// Reject tokens issued (`iat` claim) more than `max_age` ago.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.options.max_age = Some(max_age);
+        self
+    }
+
+    /// Enable or disable not-before (`nbf`) validation.
+    pub fn validate_nbf(mut self, validate_nbf: bool) -> Self {
+        self.options.validate_nbf = validate_nbf;
+        self
+    }
+
+    /// Set the claims that must be present in the token.
+    pub fn required_claims<T: ToString>(mut self, claims: &[T]) -> Self {
+        self.options.required_claims = Some(claims.iter().map(ToString::to_string).collect());
+        self
+    }
+
+    /// Set the accepted values of the `typ` header.
+    pub fn accepted_typ<T: ToString>(mut self, typ: &[T]) -> Self {
+        self.options.accepted_typ = Some(typ.iter().map(ToString::to_string).collect());
+        self
+    }
+
+    /// Accept ECDSA signatures encoded as ASN.1 DER.
+    pub fn der_ecdsa_signatures(mut self, accept_der_ecdsa_signatures: bool) -> Self {
+        self.options.accept_der_ecdsa_signatures = accept_der_ecdsa_signatures;
+        self
+    }
+
+    /// Use `provider` instead of the system clock as the current time.
+    pub fn time_provider<F: Fn() -> u64 + Send + Sync + 'static>(mut self, provider: F) -> Self {
+        self.options.time_provider = Some(TimeProvider::new(provider));
+        self
+    }
+
+    /// Validate the claims of tokens issued by `issuer` with `profile`.
+    pub fn issuer_profile<T: ToString>(mut self, issuer: T, profile: ValidationOptions) -> Self {
+        self.options
+            .issuer_profiles
+            .insert(issuer.to_string(), profile);
+        self
+    }
+
+    /// Accept time claims (`exp`, `nbf`, `iat`) encoded as numeric strings.
+    pub fn lenient_numeric_claims(mut self, lenient_numeric_claims: bool) -> Self {
+        self.options.lenient_numeric_claims = lenient_numeric_claims;
+        self
+    }
+
+    /// Accept `alg` header values that only differ in ASCII case.
+    pub fn case_insensitive_alg(mut self, case_insensitive_alg: bool) -> Self {
+        self.options.case_insensitive_alg = case_insensitive_alg;
+        self
+    }
+
+    /// Reject tokens carrying unknown header parameters.
+    pub fn reject_unknown_headers(mut self, reject_unknown_headers: bool) -> Self {
+        self.options.reject_unknown_headers = reject_unknown_headers;
+        self
+    }
+
+    /// Accept token segments with `=` padding.
+    pub fn lenient_base64(mut self, lenient_base64: bool) -> Self {
+        self.options.lenient_base64 = lenient_base64;
+        self
+    }
+
+    /// Run `validator` on the claims after the standard checks pass.
+    pub fn custom_validator<F: Fn(&Value) -> Result<(), Error> + Send + Sync + 'static>(
+        mut self,
+        validator: F,
+    ) -> Self {
+        self.options
+            .custom_validators
+            .push(ClaimValidator::new(validator));
+        self
+    }
+
+    /// Finish building the `ValidationOptions`.
+    pub fn build(self) -> ValidationOptions {
+        self.options
+    }
 }
 
 impl Default for ValidationOptions {
     fn default() -> Self {
         Self {
-            leeway: 0,
-            validate_exp: true,
+            leeway: default_leeway(),
+            exp_validation: ExpValidation::Required,
             validate_nbf: false,
+            validate_nbf_if_present: false,
+            max_age: None,
             audiences: None,
+            required_audiences: None,
+            audience_case_insensitive: false,
+            validate_aud: true,
             issuer: None,
+            issuer_validator: None,
             subject: None,
             algorithms: HashSet::new(),
             required_claims: None,
+            accepted_typ: None,
+            accept_der_ecdsa_signatures: false,
+            time_provider: None,
+            issuer_profiles: HashMap::new(),
+            lenient_numeric_claims: false,
+            case_insensitive_alg: false,
+            reject_unknown_headers: false,
+            lenient_base64: false,
+            custom_validators: Vec::new(),
         }
     }
 }
@@ -142,24 +673,100 @@ pub(crate) fn validate_header(
     {
         return Err(Error::InvalidAlgorithm);
     }
+
+    if validation_options.reject_unknown_headers {
+        if let Some(name) = header.extras.keys().next() {
+            return Err(Error::UnknownHeaderParameter(name.clone()));
+        }
+    }
+
+    if let Some(ref accepted_typ) = validation_options.accepted_typ {
+        let typ = header.typ.as_deref().ok_or(Error::InvalidType)?;
+        if !accepted_typ
+            .iter()
+            .any(|accepted| normalize_typ(accepted) == normalize_typ(typ))
+        {
+            return Err(Error::InvalidType);
+        }
+    }
+
+    if let Some(leaf_certificate) = header.x5c.as_ref().and_then(|x5c| x5c.first()) {
+        validate_certificate_thumbprints(header, leaf_certificate)?;
+    }
     Ok(())
 }
 
+/// Checks that the `x5t` and `x5t#S256` thumbprints, when present, are the
+/// base64url encoded SHA-1 and SHA-256 digests of the DER encoding of
+/// `leaf_certificate`, the first `x5c` entry. `x5c` entries use standard
+/// base64 (RFC 7515, section 4.1.6).
+fn validate_certificate_thumbprints(header: &Header, leaf_certificate: &str) -> Result<(), Error> {
+    if header.x5t.is_none() && header.x5t_s256.is_none() {
+        return Ok(());
+    }
+
+    let certificate = base64::engine::general_purpose::STANDARD.decode(leaf_certificate)?;
+    let matches = |thumbprint: Option<&str>, digest: &[u8]| {
+        thumbprint.is_none_or(|thumbprint| {
+            thumbprint == base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest)
+        })
+    };
+    if matches(
+        header.x5t.as_deref(),
+        &<sha1::Sha1 as sha1::Digest>::digest(&certificate),
+    ) && matches(header.x5t_s256.as_deref(), &Sha256::digest(&certificate))
+    {
+        Ok(())
+    } else {
+        Err(Error::CertificateThumbprintMismatch)
+    }
+}
+
+/// Normalizes a media type for comparison as described in RFC 7515, section
+/// 4.1.9: case-insensitive, with the `application/` prefix being optional.
+fn normalize_typ(typ: &str) -> String {
+    let typ = typ.to_ascii_lowercase();
+    match typ.strip_prefix("application/") {
+        Some(stripped) => stripped.to_string(),
+        None => typ,
+    }
+}
+
+/// Validates already decoded claims against `options`, without verifying
+/// any signature, e.g. when the signature was checked by an HSM. The
+/// algorithm and header options are not used.
+///
+/// Returns `Error::MalformedToken` if `claims` isn't a JSON object, like
+/// [`decode`](crate::decode) does for such payloads.
+pub fn validate_claims(claims: &Value, options: &ValidationOptions) -> Result<(), Error> {
+    validate(claims.as_object().ok_or(Error::MalformedToken)?, options)
+}
+
 /// Validates the claims within a JWT using the given `ValidationOptions`.
 pub(crate) fn validate(
     claims: &Map<String, Value>,
     options: &ValidationOptions,
 ) -> Result<(), Error> {
-    let now = current_timestamp();
+    if let Some(profile) = claims
+        .get("iss")
+        .and_then(|iss| iss.as_str())
+        .and_then(|iss| options.issuer_profiles.get(iss))
+    {
+        return validate(claims, profile);
+    }
+
+    let now = options.now();
 
     let validate_time_claim = |claim_value: Option<&Value>,
-                               validate: bool,
+                               required: bool,
                                validation_predicate: &dyn Fn(u64) -> bool,
                                validation_error: Error,
                                missing_claim_error: Error|
      -> Result<(), Error> {
-        if validate {
-            if let Some(value) = claim_value.and_then(|v| v.as_u64()) {
+        if required || claim_value.is_some() {
+            if let Some(value) =
+                claim_value.and_then(|v| time_claim_value(v, options.lenient_numeric_claims))
+            {
                 if !validation_predicate(value) {
                     return Err(validation_error);
                 }
@@ -171,21 +778,35 @@ pub(crate) fn validate(
     };
 
     validate_time_claim(
-        claims.get("exp"),
-        options.validate_exp,
+        claims
+            .get("exp")
+            .filter(|_| options.exp_validation != ExpValidation::Ignore),
+        options.exp_validation == ExpValidation::Required,
         &|timestamp| now <= timestamp + options.leeway,
         Error::ExpiredSignature,
         Error::InvalidClaim("Missing exp claim".to_string()),
     )?;
 
     validate_time_claim(
-        claims.get("nbf"),
+        claims
+            .get("nbf")
+            .filter(|_| options.validate_nbf || options.validate_nbf_if_present),
         options.validate_nbf,
         &|timestamp| now >= timestamp - options.leeway,
         Error::ImmatureSignature,
         Error::InvalidClaim("Missing nbf claim".to_string()),
     )?;
 
+    if let Some(max_age) = options.max_age {
+        validate_time_claim(
+            claims.get("iat"),
+            true,
+            &|timestamp| now.saturating_sub(timestamp) <= max_age.as_secs() + options.leeway,
+            Error::TokenTooOld,
+            Error::InvalidClaim("Missing iat claim".to_string()),
+        )?;
+    }
+
     let validate_str_claim = |claim_value: Option<&Value>,
                               expected_value: &Option<String>,
                               validation_error: Error|
@@ -202,53 +823,115 @@ pub(crate) fn validate(
         Ok(())
     };
 
-    validate_str_claim(claims.get("iss"), &options.issuer, Error::InvalidIssuer)?;
-    validate_str_claim(claims.get("sub"), &options.subject, Error::InvalidSubject)?;
-
-    let validate_audiences = |aud_claim: Option<&Value>,
-                              expected_audiences: &Option<HashSet<String>>|
-     -> Result<(), Error> {
-        if let Some(expected) = expected_audiences {
-            match aud_claim {
-                Some(Value::String(aud)) => {
-                    if !expected.contains(aud) {
-                        return Err(Error::InvalidAudience);
-                    }
-                }
-                Some(Value::Array(aud_array)) => {
-                    let provided: HashSet<String> = aud_array
-                        .iter()
-                        .filter_map(|val| val.as_str().map(String::from))
-                        .collect();
-                    if provided.is_disjoint(expected) {
-                        return Err(Error::InvalidAudience);
-                    }
-                }
-                _ => return Err(Error::InvalidAudience),
-            }
+    if let Some(ref issuer_validator) = options.issuer_validator {
+        match claims.get("iss").and_then(|iss| iss.as_str()) {
+            Some(iss) if issuer_validator.accepts(iss) => {}
+            _ => return Err(Error::InvalidIssuer),
         }
-        Ok(())
-    };
+    } else {
+        validate_str_claim(claims.get("iss"), &options.issuer, Error::InvalidIssuer)?;
+    }
+    validate_str_claim(claims.get("sub"), &options.subject, Error::InvalidSubject)?;
 
-    validate_audiences(claims.get("aud"), &options.audiences)?;
+    if options.validate_aud {
+        validate_audience(claims, options)?;
+    }
 
     if let Some(ref required_claims) = options.required_claims {
         for claim in required_claims {
             if !claims.contains_key(claim) {
-                return Err(Error::InvalidClaim(format!(
-                    "Missing required claim: {}",
-                    claim
-                )));
+                return Err(Error::MissingRequiredClaim(claim.clone()));
             }
         }
     }
 
+    if !options.custom_validators.is_empty() {
+        let claims = Value::Object(claims.clone());
+        for validator in &options.custom_validators {
+            validator.validate(&claims)?;
+        }
+    }
+
     Ok(())
 }
 
+/// Checks the `aud` claim against the acceptable and required audiences.
+fn validate_audience(
+    claims: &Map<String, Value>,
+    options: &ValidationOptions,
+) -> Result<(), Error> {
+    let normalize_audience = |aud: &str| -> String {
+        if options.audience_case_insensitive {
+            aud.to_ascii_lowercase()
+        } else {
+            aud.to_string()
+        }
+    };
+
+    let provided_audiences: Option<HashSet<String>> = match claims.get("aud") {
+        Some(Value::String(aud)) => Some(HashSet::from([normalize_audience(aud)])),
+        Some(Value::Array(aud_array)) => Some(
+            aud_array
+                .iter()
+                .filter_map(Value::as_str)
+                .map(normalize_audience)
+                .collect(),
+        ),
+        _ => None,
+    };
+
+    if let Some(ref expected_audiences) = options.audiences {
+        let expected: HashSet<String> = expected_audiences
+            .iter()
+            .map(|aud| normalize_audience(aud))
+            .collect();
+        match provided_audiences {
+            Some(ref provided) if !provided.is_disjoint(&expected) => {}
+            _ => return Err(Error::InvalidAudience),
+        }
+    }
+
+    if let Some(ref required_audiences) = options.required_audiences {
+        if let Some(missing) = required_audiences
+            .iter()
+            .filter(|aud| {
+                !provided_audiences
+                    .as_ref()
+                    .is_some_and(|provided| provided.contains(&normalize_audience(aud)))
+            })
+            .min()
+        {
+            return Err(Error::MissingRequiredAudience(missing.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads a NumericDate time claim as whole seconds. Fractional values are
+/// truncated, as some issuers emit them despite RFC 7519 expecting integers.
+/// Numeric strings are only parsed when `lenient` is set.
+pub(crate) fn time_claim_value(value: &Value, lenient: bool) -> Option<u64> {
+    let whole_seconds = |seconds: f64| {
+        Some(seconds)
+            .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+            .map(|seconds| seconds.trunc() as u64)
+    };
+
+    match value {
+        Value::String(value) if lenient => value
+            .parse::<u64>()
+            .ok()
+            .or_else(|| value.parse::<f64>().ok().and_then(whole_seconds)),
+        value => value
+            .as_u64()
+            .or_else(|| value.as_f64().and_then(whole_seconds)),
+    }
+}
+
 /// Gets the current timestamp in seconds since the UNIX epoch.
 #[cfg(not(target_arch = "wasm32"))]
-fn current_timestamp() -> u64 {
+pub(crate) fn current_timestamp() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .expect("SystemTime before UNIX EPOCH")
@@ -265,6 +948,88 @@ mod tests {
     use super::*;
     use serde_json::{json, to_value};
 
+    #[test]
+    fn test_only_timestamps() {
+        let now = current_timestamp();
+        let options = ValidationOptions::only_timestamps(30);
+        assert_eq!(options.leeway, 30);
+
+        let claims = json!({ "exp": now + 3600, "nbf": now, "aud": "other", "iss": "anyone" });
+        assert!(validate_claims(&claims, &options).is_ok());
+        assert!(validate_claims(&claims, &options.clone().with_audience("api")).is_ok());
+
+        let expired = json!({ "exp": now - 3600, "nbf": now - 7200, "aud": "other" });
+        assert!(matches!(
+            validate_claims(&expired, &options),
+            Err(Error::ExpiredSignature)
+        ));
+
+        let immature = json!({ "exp": now + 3600, "nbf": now + 3600 });
+        assert!(matches!(
+            validate_claims(&immature, &options),
+            Err(Error::ImmatureSignature)
+        ));
+
+        let without_nbf = json!({ "exp": now + 3600 });
+        assert!(validate_claims(&without_nbf, &options).is_ok());
+        assert!(matches!(
+            validate_claims(&json!({ "nbf": now }), &options),
+            Err(Error::InvalidClaim(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_claims() {
+        let now = current_timestamp();
+        let claims = json!({
+            "exp": now + 3600,
+            "nbf": now - 60,
+            "aud": "api",
+            "iss": "https://issuer.example",
+        });
+        let options = ValidationOptions::default()
+            .with_audience("api")
+            .with_issuer("https://issuer.example");
+        let options = ValidationOptions {
+            validate_nbf: true,
+            ..options
+        };
+        assert!(validate_claims(&claims, &options).is_ok());
+
+        let mut expired = claims.clone();
+        expired["exp"] = json!(now - 3600);
+        assert!(matches!(
+            validate_claims(&expired, &options),
+            Err(Error::ExpiredSignature)
+        ));
+
+        let mut immature = claims.clone();
+        immature["nbf"] = json!(now + 3600);
+        assert!(matches!(
+            validate_claims(&immature, &options),
+            Err(Error::ImmatureSignature)
+        ));
+
+        let mut wrong_audience = claims.clone();
+        wrong_audience["aud"] = json!("other");
+        assert!(matches!(
+            validate_claims(&wrong_audience, &options),
+            Err(Error::InvalidAudience)
+        ));
+
+        let mut wrong_issuer = claims.clone();
+        wrong_issuer["iss"] = json!("https://other.example");
+        assert!(matches!(
+            validate_claims(&wrong_issuer, &options),
+            Err(Error::InvalidIssuer)
+        ));
+
+        assert!(matches!(
+            validate_claims(&json!(["not", "an", "object"]), &options),
+            Err(Error::MalformedToken)
+        ));
+    }
+
     #[test]
     fn test_expiration_validation() {
         let mut claims = Map::new();
@@ -280,6 +1045,148 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_exp_validation_modes() {
+        let missing = Map::new();
+        let mut expired = Map::new();
+        expired.insert(
+            "exp".to_string(),
+            to_value(current_timestamp() - 3600).unwrap(),
+        );
+        let mut valid = Map::new();
+        valid.insert(
+            "exp".to_string(),
+            to_value(current_timestamp() + 3600).unwrap(),
+        );
+
+        let required = ValidationOptions::default().with_exp_validation(ExpValidation::Required);
+        assert_eq!(required, ValidationOptions::default());
+        assert!(validate(&valid, &required).is_ok());
+        assert!(matches!(
+            validate(&expired, &required),
+            Err(Error::ExpiredSignature)
+        ));
+        assert!(matches!(
+            validate(&missing, &required),
+            Err(Error::InvalidClaim(_))
+        ));
+
+        let optional =
+            ValidationOptions::default().with_exp_validation(ExpValidation::OptionalIfPresent);
+        assert!(validate(&valid, &optional).is_ok());
+        assert!(matches!(
+            validate(&expired, &optional),
+            Err(Error::ExpiredSignature)
+        ));
+        assert!(validate(&missing, &optional).is_ok());
+
+        let ignore = ValidationOptions::builder()
+            .exp_validation(ExpValidation::Ignore)
+            .build();
+        assert!(validate(&valid, &ignore).is_ok());
+        assert!(validate(&expired, &ignore).is_ok());
+        assert!(validate(&missing, &ignore).is_ok());
+    }
+
+    #[test]
+    fn test_max_age_validation() {
+        let options = ValidationOptions::default().with_max_age(Duration::from_secs(3600));
+        let claims_issued_at = |iat: u64| {
+            let mut claims = Map::new();
+            claims.insert(
+                "exp".to_string(),
+                to_value(current_timestamp() + 3600).unwrap(),
+            );
+            claims.insert("iat".to_string(), to_value(iat).unwrap());
+            claims
+        };
+
+        let fresh = claims_issued_at(current_timestamp() - 60);
+        assert!(validate(&fresh, &options).is_ok());
+
+        let old = claims_issued_at(current_timestamp() - 7200);
+        assert!(matches!(validate(&old, &options), Err(Error::TokenTooOld)));
+        assert!(validate(&old, &options.clone().with_leeway(7200)).is_ok());
+        assert!(validate(&old, &ValidationOptions::default()).is_ok());
+
+        let mut missing_iat = fresh.clone();
+        missing_iat.remove("iat");
+        assert!(matches!(
+            validate(&missing_iat, &options),
+            Err(Error::InvalidClaim(_))
+        ));
+    }
+
+    #[test]
+    fn test_float_time_claims() {
+        let mut claims = Map::new();
+        claims.insert(
+            "exp".to_string(),
+            json!(current_timestamp() as f64 + 3600.75),
+        );
+        claims.insert("nbf".to_string(), json!(current_timestamp() as f64 - 0.5));
+        let options = ValidationOptions {
+            validate_nbf: true,
+            ..ValidationOptions::default()
+        };
+        assert!(validate(&claims, &options).is_ok());
+
+        claims.insert("exp".to_string(), json!(current_timestamp() as f64 - 60.5));
+        assert!(matches!(
+            validate(&claims, &options),
+            Err(Error::ExpiredSignature)
+        ));
+
+        assert_eq!(
+            time_claim_value(&json!(1700000000.9), false),
+            Some(1700000000)
+        );
+        assert_eq!(time_claim_value(&json!(-1.0), false), None);
+    }
+
+    #[test]
+    fn test_lenient_numeric_time_claims() {
+        let mut claims = Map::new();
+        claims.insert(
+            "exp".to_string(),
+            json!((current_timestamp() + 3600).to_string()),
+        );
+
+        let result = validate(&claims, &ValidationOptions::default());
+        assert!(matches!(result, Err(Error::InvalidClaim(_))));
+
+        let options = ValidationOptions::default().with_lenient_numeric_claims(true);
+        assert!(validate(&claims, &options).is_ok());
+
+        claims.insert(
+            "exp".to_string(),
+            json!((current_timestamp() - 60).to_string()),
+        );
+        assert!(matches!(
+            validate(&claims, &options),
+            Err(Error::ExpiredSignature)
+        ));
+
+        claims.insert("exp".to_string(), json!("soon"));
+        assert!(matches!(
+            validate(&claims, &options),
+            Err(Error::InvalidClaim(_))
+        ));
+    }
+
+    #[test]
+    fn test_default_leeway() {
+        assert_eq!(ValidationOptions::default().leeway, DEFAULT_LEEWAY_SECS);
+        assert_eq!(ValidationBuilder::new().build().leeway, DEFAULT_LEEWAY_SECS);
+        assert_eq!(
+            ValidationOptions::default()
+                .with_leeway(DEFAULT_LEEWAY_SECS + 30)
+                .with_default_leeway()
+                .leeway,
+            DEFAULT_LEEWAY_SECS
+        );
+    }
+
     #[test]
     fn test_expiration_validation_fail() {
         let mut claims = Map::new();
@@ -334,6 +1241,107 @@ mod tests {
         assert!(matches!(result, Err(Error::InvalidIssuer)));
     }
 
+    #[test]
+    fn test_clone_and_modify() {
+        let base = ValidationOptions::new(Algorithm::RS256)
+            .with_issuer("https://login.example.com/")
+            .with_issuer_validator(|iss| iss.ends_with(".example.com/"))
+            .with_time_provider(|| 1_700_000_000)
+            .with_custom_validator(|_| Ok(()));
+
+        let admin_route = base
+            .clone()
+            .with_audience("admin-api")
+            .with_required_claim("scope");
+        assert_eq!(base.audiences, None);
+        assert_eq!(base.required_claims, None);
+        assert_eq!(admin_route.issuer, base.issuer);
+        assert_eq!(admin_route.issuer_validator, base.issuer_validator);
+        assert_eq!(admin_route.now(), 1_700_000_000);
+        assert_eq!(admin_route.custom_validators.len(), 1);
+        assert_ne!(admin_route, base);
+
+        let debug = format!("{:?}", admin_route);
+        assert!(debug.contains("IssuerValidator(<fn>)"));
+        assert!(debug.contains("TimeProvider(<fn>)"));
+        assert!(debug.contains("ClaimValidator(<fn>)"));
+    }
+
+    #[test]
+    fn test_custom_validator() {
+        let options = ValidationOptions::default().with_custom_validator(|claims| {
+            let scopes = claims.get("scope").and_then(Value::as_str).unwrap_or("");
+            if scopes.split(' ').any(|scope| scope == "admin") {
+                Ok(())
+            } else {
+                Err(Error::ClaimValidation(
+                    "scope must contain admin".to_string(),
+                ))
+            }
+        });
+        let claims_with_scope = |scope: &str| {
+            let mut claims = Map::new();
+            claims.insert(
+                "exp".to_string(),
+                to_value(current_timestamp() + 3600).unwrap(),
+            );
+            claims.insert("scope".to_string(), to_value(scope).unwrap());
+            claims
+        };
+
+        assert!(validate(&claims_with_scope("read admin"), &options).is_ok());
+        let result = validate(&claims_with_scope("read write"), &options);
+        assert!(
+            matches!(result, Err(Error::ClaimValidation(ref reason)) if reason.contains("admin"))
+        );
+
+        // The standard checks run first.
+        let mut expired = claims_with_scope("admin");
+        expired.insert("exp".to_string(), to_value(1).unwrap());
+        assert!(matches!(
+            validate(&expired, &options),
+            Err(Error::ExpiredSignature)
+        ));
+    }
+
+    #[test]
+    fn test_issuer_validator() {
+        let options = ValidationOptions::default()
+            .with_issuer("https://login.example.com/")
+            .with_issuer_validator(|iss| {
+                iss.strip_prefix("https://")
+                    .and_then(|host| host.strip_suffix(".example.com/"))
+                    .is_some_and(|tenant| !tenant.is_empty() && !tenant.contains('/'))
+            });
+        let claims_with_issuer = |iss: &str| {
+            let mut claims = Map::new();
+            claims.insert(
+                "exp".to_string(),
+                to_value(current_timestamp() + 3600).unwrap(),
+            );
+            claims.insert("iss".to_string(), to_value(iss).unwrap());
+            claims
+        };
+
+        for iss in [
+            "https://tenant-a.example.com/",
+            "https://tenant-b.example.com/",
+        ] {
+            assert!(validate(&claims_with_issuer(iss), &options).is_ok());
+        }
+        for iss in ["https://example.com/", "https://evil.com/.example.com/"] {
+            let result = validate(&claims_with_issuer(iss), &options);
+            assert!(matches!(result, Err(Error::InvalidIssuer)));
+        }
+
+        let mut claims = claims_with_issuer("https://tenant-a.example.com/");
+        claims.remove("iss");
+        assert!(matches!(
+            validate(&claims, &options),
+            Err(Error::InvalidIssuer)
+        ));
+    }
+
     #[test]
     fn test_subject_validation() {
         let mut claims = Map::new();
@@ -424,6 +1432,89 @@ mod tests {
         assert!(matches!(result, Err(Error::InvalidAudience)));
     }
 
+    #[test]
+    fn test_audience_validation_case_insensitive() {
+        let mut claims = Map::new();
+        claims.insert(
+            "exp".to_string(),
+            to_value(current_timestamp() + 3600).unwrap(),
+        );
+        claims.insert("aud".to_string(), json!("https://API.example.com"));
+
+        let options = ValidationOptions::default().with_audience("https://api.example.com");
+        let result = validate(&claims, &options);
+        assert!(matches!(result, Err(Error::InvalidAudience)));
+
+        let options = options.with_audience_case_insensitive(true);
+        let result = validate(&claims, &options);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_audience_validation_disabled() {
+        let mut claims = Map::new();
+        claims.insert(
+            "exp".to_string(),
+            to_value(current_timestamp() + 3600).unwrap(),
+        );
+
+        let options = ValidationOptions::default()
+            .with_audience("valid_audience")
+            .with_required_audiences(&["valid_audience"]);
+        assert!(matches!(
+            validate(&claims, &options),
+            Err(Error::InvalidAudience)
+        ));
+
+        let options = options.with_validate_aud(false);
+        assert!(validate(&claims, &options).is_ok());
+
+        claims.insert("aud".to_string(), json!("other_audience"));
+        assert!(validate(&claims, &options).is_ok());
+
+        let options = ValidationOptions::builder()
+            .audiences(&["valid_audience"])
+            .validate_aud(false)
+            .build();
+        assert!(validate(&claims, &options).is_ok());
+        assert!(validate(&claims, &options.with_validate_aud(true)).is_err());
+    }
+
+    #[test]
+    fn test_required_audiences_validation() {
+        let mut claims = Map::new();
+        claims.insert(
+            "exp".to_string(),
+            to_value(current_timestamp() + 3600).unwrap(),
+        );
+        claims.insert(
+            "aud".to_string(),
+            json!(["first_audience", "second_audience", "third_audience"]),
+        );
+
+        let options = ValidationOptions::default()
+            .with_required_audiences(&["first_audience", "second_audience"]);
+        let result = validate(&claims, &options);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_required_audiences_validation_partial_match_fail() {
+        let mut claims = Map::new();
+        claims.insert(
+            "exp".to_string(),
+            to_value(current_timestamp() + 3600).unwrap(),
+        );
+        claims.insert("aud".to_string(), json!(["first_audience", "other"]));
+
+        let options = ValidationOptions::default()
+            .with_required_audiences(&["first_audience", "second_audience"]);
+        let result = validate(&claims, &options);
+        assert!(
+            matches!(result, Err(Error::MissingRequiredAudience(ref aud)) if aud == "second_audience")
+        );
+    }
+
     #[test]
     fn test_algorithm_validation() {
         let header = Header {
@@ -458,6 +1549,51 @@ mod tests {
         assert!(matches!(result, Err(Error::InvalidAlgorithm)));
     }
 
+    #[test]
+    fn test_accepted_typ_validation() {
+        let options = ValidationOptions::default().with_accepted_typ(&["at+jwt"]);
+
+        for typ in ["at+jwt", "application/at+jwt", "Application/AT+JWT"] {
+            let header = Header {
+                typ: Some(typ.to_string()),
+                ..Header::default()
+            };
+            assert!(validate_header(&header, &options).is_ok(), "{}", typ);
+        }
+    }
+
+    #[test]
+    fn test_accepted_typ_validation_fail() {
+        let options =
+            ValidationOptions::default().with_accepted_typ(&["at+jwt", "application/at+jwt"]);
+
+        let header = Header::default();
+        let result = validate_header(&header, &options);
+        assert!(matches!(result, Err(Error::InvalidType)));
+
+        let header = Header {
+            typ: None,
+            ..Header::default()
+        };
+        let result = validate_header(&header, &options);
+        assert!(matches!(result, Err(Error::InvalidType)));
+    }
+
+    #[test]
+    fn test_reject_unknown_headers() {
+        let mut header = Header::default();
+        header.extras.insert("cty2".to_string(), json!("custom"));
+
+        let options = ValidationOptions::default();
+        assert!(validate_header(&header, &options).is_ok());
+
+        let options = options.with_reject_unknown_headers(true);
+        let result = validate_header(&header, &options);
+        assert!(matches!(result, Err(Error::UnknownHeaderParameter(name)) if name == "cty2"));
+
+        assert!(validate_header(&Header::default(), &options).is_ok());
+    }
+
     #[test]
     fn test_required_claims() {
         let mut claims = Map::new();
@@ -482,7 +1618,7 @@ mod tests {
 
         let options = ValidationOptions::default().with_required_claim("sub");
         let result = validate(&claims, &options);
-        assert!(matches!(result, Err(Error::InvalidClaim(_))));
+        assert!(matches!(result, Err(Error::MissingRequiredClaim(ref claim)) if claim == "sub"));
     }
 
     #[test]
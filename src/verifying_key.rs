@@ -1,5 +1,15 @@
-use crate::{Error, PemEncodedKey, SecretKey};
+use crate::{Algorithm, Error, Jwk, PemEncodedKey, SecretKey};
 use base64::Engine;
+use rsa::traits::PublicKeyParts;
+
+/// Verifies JWT signatures, allowing `decode` to be used with key backends
+/// other than [`VerifyingKey`], such as an HSM or a remote service.
+pub trait JwtVerifier {
+    /// Verifies `signature` over `signing_input` (the encoded header and
+    /// payload segments joined by `.`) for the algorithm `alg`. ECDSA
+    /// signatures are always passed in their fixed-width `r || s` form.
+    fn verify(&self, alg: &Algorithm, signing_input: &str, signature: &[u8]) -> Result<(), Error>;
+}
 
 /// A verification key used to verify the signature of a JWT.
 #[derive(Debug, Clone)]
@@ -26,11 +36,30 @@ impl VerifyingKey {
         Ok(Self::RsaKey(key))
     }
 
+    /// Builds an RSA key from the big-endian modulus and exponent. Both are
+    /// read as unsigned integers, so a leading zero sign byte in `n`, as
+    /// some JWKs carry, yields the same key.
     pub fn from_rsa_components(n: &[u8], e: &[u8]) -> Result<Self, Error> {
         let key = PemEncodedKey::from_rsa_components(n, e)?;
         Ok(Self::RsaKey(key))
     }
 
+    /// Builds an EdDSA key from a raw 32-byte Ed25519 public key.
+    pub fn from_ed25519_public(public_key: &[u8; 32]) -> Result<Self, Error> {
+        let key = PemEncodedKey::from_ed25519_public(public_key)?;
+        Ok(Self::EdKey(key))
+    }
+
+    /// The kind of key, as reported in `Error::KeyAlgorithmMismatch`.
+    pub(crate) fn key_kind(&self) -> &'static str {
+        match self {
+            Self::Secret(_) => "secret",
+            Self::RsaKey(_) => "RSA",
+            Self::EcKey(_) => "EC",
+            Self::EdKey(_) => "Ed25519",
+        }
+    }
+
     pub fn from_rsa_der(key: &[u8]) -> Result<Self, Error> {
         let key = PemEncodedKey::new(key)?;
         Ok(Self::RsaKey(key))
@@ -55,4 +84,97 @@ impl VerifyingKey {
         let key = PemEncodedKey::new(key)?;
         Ok(Self::EdKey(key))
     }
+
+    /// Builds a verification key from a JWK. `RSA`, `EC`, `oct` and `OKP`
+    /// keys are supported, the latter only on the `Ed25519` curve. Keys
+    /// declared for encryption are rejected with [`Error::KeyNotForSigning`].
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        let decode_member = |member: &Option<String>| -> Result<Vec<u8>, Error> {
+            let value = member.as_deref().ok_or(Error::InvalidKeyFormat)?;
+            Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(value)?)
+        };
+
+        jwk.check_signature_use()?;
+        match jwk.kty.as_str() {
+            "RSA" => Self::from_rsa_components(&decode_member(&jwk.n)?, &decode_member(&jwk.e)?),
+            "EC" => {
+                let crv = jwk.crv.as_deref().ok_or(Error::InvalidKeyFormat)?;
+                let key = PemEncodedKey::from_ec_components(
+                    crv,
+                    &decode_member(&jwk.x)?,
+                    &decode_member(&jwk.y)?,
+                )?;
+                Ok(Self::EcKey(key))
+            }
+            "oct" => Ok(Self::from_secret(&decode_member(&jwk.k)?)),
+            "OKP" => match jwk.crv.as_deref().ok_or(Error::InvalidKeyFormat)? {
+                "Ed25519" => {
+                    let x = decode_member(&jwk.x)?;
+                    Self::from_ed25519_public(
+                        x.as_slice()
+                            .try_into()
+                            .map_err(|_| Error::InvalidKeyFormat)?,
+                    )
+                }
+                "Ed448" => Err(Error::UnsupportedKeyType("Ed448".to_string())),
+                _ => Err(Error::UnsupportedKeyType("OKP".to_string())),
+            },
+            kty => Err(Error::UnsupportedKeyType(kty.to_string())),
+        }
+    }
+
+    /// Exports the public key, or the secret of an HMAC key, as a JWK. EC
+    /// coordinates always have the full size of the curve.
+    pub fn to_jwk(&self) -> Result<Jwk, Error> {
+        let encode = |bytes: &[u8]| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+
+        match self {
+            Self::Secret(secret) => Ok(Jwk {
+                kty: "oct".to_string(),
+                k: Some(encode(secret.inner())),
+                ..Jwk::default()
+            }),
+            Self::RsaKey(key) => {
+                let public_key = key.as_rsa_public_key()?;
+                Ok(Jwk {
+                    kty: "RSA".to_string(),
+                    n: Some(encode(&public_key.n().to_bytes_be())),
+                    e: Some(encode(&public_key.e().to_bytes_be())),
+                    ..Jwk::default()
+                })
+            }
+            Self::EcKey(key) => {
+                let (crv, point) = key.ec_public_point()?;
+                let (x, y) = point[1..].split_at((point.len() - 1) / 2);
+                Ok(Jwk {
+                    kty: "EC".to_string(),
+                    crv: Some(crv.to_string()),
+                    x: Some(encode(x)),
+                    y: Some(encode(y)),
+                    ..Jwk::default()
+                })
+            }
+            Self::EdKey(key) => Ok(Jwk {
+                kty: "OKP".to_string(),
+                crv: Some("Ed25519".to_string()),
+                x: Some(encode(key.as_ed_public_key()?)),
+                ..Jwk::default()
+            }),
+        }
+    }
+
+    /// Parses a single JWK from its JSON representation and builds a
+    /// verification key from it.
+    pub fn from_jwk_json(jwk: &str) -> Result<Self, Error> {
+        let jwk: Jwk = serde_json::from_str(jwk)?;
+        Self::from_jwk(&jwk)
+    }
+}
+
+impl TryFrom<&Jwk> for VerifyingKey {
+    type Error = Error;
+
+    fn try_from(jwk: &Jwk) -> Result<Self, Self::Error> {
+        Self::from_jwk(jwk)
+    }
 }
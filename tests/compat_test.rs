@@ -0,0 +1,184 @@
+use jwt_rustcrypto::compat::{
+    decode, decode_header, encode, DecodingKey, EncodingKey, Header, Validation,
+};
+use jwt_rustcrypto::{Algorithm, Error};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const TEST_KEYS_DIR: &str = "tests/keys";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    company: String,
+    exp: u64,
+}
+
+fn load_key(file_name: &str) -> String {
+    let path = Path::new(TEST_KEYS_DIR).join(file_name);
+    fs::read_to_string(path).expect("Failed to read key file")
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("SystemTime before UNIX EPOCH")
+        .as_secs()
+}
+
+#[test]
+fn test_compat_hs256_round_trip() {
+    let my_claims = Claims {
+        sub: "b@b.com".to_owned(),
+        company: "ACME".to_owned(),
+        exp: current_timestamp() + 3600,
+    };
+    let key = b"secret";
+
+    let token = encode(
+        &Header::default(),
+        &my_claims,
+        &EncodingKey::from_secret(key),
+    )
+    .unwrap();
+    let token_data = decode::<Claims>(
+        &token,
+        &DecodingKey::from_secret(key),
+        &Validation::default(),
+    )
+    .unwrap();
+
+    assert_eq!(token_data.claims.sub, "b@b.com");
+    assert_eq!(token_data.header.alg, Algorithm::HS256);
+    assert_eq!(decode_header(&token).unwrap().alg, Algorithm::HS256);
+}
+
+#[test]
+fn test_compat_rs256_with_audience_and_issuer() {
+    let claims = serde_json::json!({
+        "sub": "b@b.com",
+        "company": "ACME",
+        "aud": "me",
+        "iss": "issuer",
+        "exp": current_timestamp() + 3600,
+    });
+    let header = Header::new(Algorithm::RS256);
+    let encoding_key =
+        EncodingKey::from_rsa_pem(load_key("rsa_private_key_pkcs8.pem").as_bytes()).unwrap();
+    let decoding_key =
+        DecodingKey::from_rsa_pem(load_key("rsa_public_key_pkcs8.pem").as_bytes()).unwrap();
+    let token = encode(&header, &claims, &encoding_key).unwrap();
+
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.set_audience(&["me"]);
+    validation.set_issuer(&["issuer", "other_issuer"]);
+    let token_data = decode::<Claims>(&token, &decoding_key, &validation).unwrap();
+    assert_eq!(token_data.claims.company, "ACME");
+
+    validation.set_issuer(&["other_issuer"]);
+    let result = decode::<Claims>(&token, &decoding_key, &validation);
+    assert!(matches!(result, Err(Error::InvalidIssuer)));
+}
+
+#[test]
+fn test_compat_expired_token_within_leeway() {
+    let claims = Claims {
+        sub: "b@b.com".to_owned(),
+        company: "ACME".to_owned(),
+        exp: current_timestamp() - 30,
+    };
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(b"secret"),
+    )
+    .unwrap();
+    let key = DecodingKey::from_secret(b"secret");
+
+    let mut validation = Validation::default();
+    assert!(decode::<Claims>(&token, &key, &validation).is_ok());
+
+    validation.leeway = 0;
+    let result = decode::<Claims>(&token, &key, &validation);
+    assert!(matches!(result, Err(Error::ExpiredSignature)));
+}
+
+#[test]
+fn test_compat_single_audience_matches_array_aud() {
+    let key = b"secret";
+    let encode_with_aud = |aud: serde_json::Value| {
+        let claims = serde_json::json!({
+            "sub": "b@b.com",
+            "company": "ACME",
+            "aud": aud,
+            "exp": current_timestamp() + 3600,
+        });
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(key)).unwrap()
+    };
+    let decoding_key = DecodingKey::from_secret(key);
+
+    // Single configured audience, array `aud` claim.
+    let mut validation = Validation::default();
+    validation.set_audience(&["x"]);
+    let token = encode_with_aud(serde_json::json!(["x", "y"]));
+    assert!(decode::<Claims>(&token, &decoding_key, &validation).is_ok());
+
+    let token = encode_with_aud(serde_json::json!(["y", "z"]));
+    let result = decode::<Claims>(&token, &decoding_key, &validation);
+    assert!(matches!(result, Err(Error::InvalidAudience)));
+
+    // Several configured audiences, single string `aud` claim.
+    validation.set_audience(&["x", "y"]);
+    let token = encode_with_aud(serde_json::json!("y"));
+    assert!(decode::<Claims>(&token, &decoding_key, &validation).is_ok());
+}
+
+#[test]
+fn test_compat_token_data_accessors() {
+    let key = b"secret";
+    let claims = Claims {
+        sub: "b@b.com".to_owned(),
+        company: "ACME".to_owned(),
+        exp: current_timestamp() + 3600,
+    };
+    let header = Header {
+        kid: Some("key-1".to_owned()),
+        ..Header::default()
+    };
+    let token = encode(&header, &claims, &EncodingKey::from_secret(key)).unwrap();
+
+    let token_data = decode::<Claims>(
+        &token,
+        &DecodingKey::from_secret(key),
+        &Validation::default(),
+    )
+    .unwrap();
+    assert_eq!(token_data.header().kid.as_deref(), Some("key-1"));
+    assert_eq!(token_data.claims().company, "ACME");
+
+    let claims = token_data.into_claims();
+    assert_eq!(claims.sub, "b@b.com");
+}
+
+#[test]
+fn test_compat_required_custom_claim() {
+    let key = b"secret";
+    let claims = Claims {
+        sub: "b@b.com".to_owned(),
+        company: "ACME".to_owned(),
+        exp: current_timestamp() + 3600,
+    };
+    let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(key)).unwrap();
+
+    let mut validation = Validation::default();
+    validation.set_required_spec_claims(&["exp", "company"]);
+    assert!(decode::<Claims>(&token, &DecodingKey::from_secret(key), &validation).is_ok());
+
+    validation.set_required_spec_claims(&["exp", "tenant_id"]);
+    assert!(matches!(
+        decode::<Claims>(&token, &DecodingKey::from_secret(key), &validation),
+        Err(Error::MissingRequiredClaim(ref claim)) if claim == "tenant_id"
+    ));
+}
@@ -0,0 +1,21 @@
+//! Sets the process-wide default leeway, so it runs in a test binary of its
+//! own.
+use jwt_rustcrypto::{ValidationBuilder, ValidationOptions, DEFAULT_LEEWAY_SECS};
+
+#[test]
+fn test_set_default_leeway() {
+    assert_eq!(ValidationOptions::default().leeway, DEFAULT_LEEWAY_SECS);
+
+    assert!(ValidationOptions::set_default_leeway(90));
+    assert!(!ValidationOptions::set_default_leeway(30));
+
+    assert_eq!(ValidationOptions::default().leeway, 90);
+    assert_eq!(ValidationBuilder::new().build().leeway, 90);
+    assert_eq!(
+        ValidationOptions::default()
+            .with_leeway(5)
+            .with_default_leeway()
+            .leeway,
+        90
+    );
+}
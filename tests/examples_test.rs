@@ -0,0 +1,7 @@
+#[path = "../examples/roundtrip.rs"]
+mod roundtrip;
+
+#[test]
+fn test_roundtrip_example() {
+    roundtrip::main().expect("roundtrip example failed");
+}
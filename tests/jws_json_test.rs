@@ -0,0 +1,152 @@
+use base64::Engine;
+use jwt_rustcrypto::{
+    decode_flattened_json, decode_json_serialization, encode, Algorithm, Error, Header, SigningKey,
+    ValidationOptions, VerifyingKey,
+};
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const TEST_KEYS_DIR: &str = "tests/keys";
+
+fn load_key(file_name: &str) -> String {
+    let path = Path::new(TEST_KEYS_DIR).join(file_name);
+    fs::read_to_string(path).expect("Failed to read key file")
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("SystemTime before UNIX EPOCH")
+        .as_secs()
+}
+
+fn general_json_jws() -> String {
+    let payload = json!({ "sub": "1234567890", "exp": current_timestamp() + 3600 });
+    let hmac_token = encode(
+        &Header::new(Algorithm::HS256),
+        &SigningKey::from_secret(b"mysecret"),
+        &payload,
+    )
+    .unwrap();
+    let rsa_token = encode(
+        &Header::new(Algorithm::RS256),
+        &SigningKey::from_rsa_pem(load_key("rsa_private_key_pkcs8.pem").as_bytes()).unwrap(),
+        &payload,
+    )
+    .unwrap();
+
+    let hmac_parts: Vec<&str> = hmac_token.split('.').collect();
+    let rsa_parts: Vec<&str> = rsa_token.split('.').collect();
+    assert_eq!(hmac_parts[1], rsa_parts[1]);
+
+    json!({
+        "payload": rsa_parts[1],
+        "signatures": [
+            { "protected": hmac_parts[0], "signature": hmac_parts[2] },
+            { "protected": rsa_parts[0], "header": { "kid": "rsa" }, "signature": rsa_parts[2] },
+        ],
+    })
+    .to_string()
+}
+
+#[test]
+fn test_decode_json_serialization_selects_matching_signature() {
+    let jws = general_json_jws();
+    let verifying_key =
+        VerifyingKey::from_rsa_pem(load_key("rsa_public_key_pkcs8.pem").as_bytes()).unwrap();
+    let validation_options = ValidationOptions::default().with_algorithm(Algorithm::RS256);
+
+    let decoded = decode_json_serialization(&jws, &verifying_key, &validation_options).unwrap();
+    assert_eq!(decoded.header.alg, Algorithm::RS256);
+    assert_eq!(decoded.payload["sub"], "1234567890");
+
+    let verifying_key = VerifyingKey::from_secret(b"mysecret");
+    let decoded =
+        decode_json_serialization(&jws, &verifying_key, &ValidationOptions::default()).unwrap();
+    assert_eq!(decoded.header.alg, Algorithm::HS256);
+}
+
+#[test]
+fn test_decode_json_serialization_no_matching_signature() {
+    let jws = general_json_jws();
+    let verifying_key = VerifyingKey::from_secret(b"othersecret");
+
+    let result = decode_json_serialization(&jws, &verifying_key, &ValidationOptions::default());
+    assert!(matches!(result, Err(Error::InvalidSignature)));
+}
+
+#[test]
+fn test_decode_json_serialization_reports_rejected_entries() {
+    let jws = general_json_jws();
+    let verifying_key = VerifyingKey::from_secret(b"mysecret");
+
+    let validation_options = ValidationOptions::new(Algorithm::ES256);
+    let result = decode_json_serialization(&jws, &verifying_key, &validation_options);
+    assert!(matches!(result, Err(Error::InvalidAlgorithm)));
+
+    let mut malformed: serde_json::Value = serde_json::from_str(&jws).unwrap();
+    malformed["signatures"][0]["protected"] = json!("not base64!");
+    malformed["signatures"][1]["protected"] = json!("not base64!");
+    let result = decode_json_serialization(
+        &malformed.to_string(),
+        &verifying_key,
+        &ValidationOptions::default(),
+    );
+    assert!(matches!(result, Err(Error::Base64EncodeDecodeError(_))));
+
+    let payload = json!({ "sub": "1234567890", "exp": current_timestamp() - 3600 });
+    let token = encode(
+        &Header::new(Algorithm::HS256),
+        &SigningKey::from_secret(b"mysecret"),
+        &payload,
+    )
+    .unwrap();
+    let parts: Vec<&str> = token.split('.').collect();
+    let expired = json!({
+        "payload": parts[1],
+        "signatures": [{ "protected": parts[0], "signature": parts[2] }],
+    })
+    .to_string();
+    let result = decode_json_serialization(&expired, &verifying_key, &ValidationOptions::default());
+    assert!(matches!(result, Err(Error::ExpiredSignature)));
+}
+
+#[test]
+fn test_decode_flattened_json() {
+    let payload = json!({ "sub": "1234567890", "exp": current_timestamp() + 3600 });
+    let token = encode(
+        &Header::new(Algorithm::RS256),
+        &SigningKey::from_rsa_pem(load_key("rsa_private_key_pkcs8.pem").as_bytes()).unwrap(),
+        &payload,
+    )
+    .unwrap();
+    let parts: Vec<&str> = token.split('.').collect();
+    let flattened = |payload: &str| {
+        json!({
+            "payload": payload,
+            "protected": parts[0],
+            "header": { "kid": "rsa" },
+            "signature": parts[2],
+        })
+        .to_string()
+    };
+    let verifying_key =
+        VerifyingKey::from_rsa_pem(load_key("rsa_public_key_pkcs8.pem").as_bytes()).unwrap();
+    let validation_options = ValidationOptions::new(Algorithm::RS256);
+
+    let decoded =
+        decode_flattened_json(&flattened(parts[1]), &verifying_key, &validation_options).unwrap();
+    assert_eq!(decoded.header.alg, Algorithm::RS256);
+    assert_eq!(decoded.payload["sub"], "1234567890");
+
+    let tampered_payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .encode(json!({ "sub": "admin", "exp": current_timestamp() + 3600 }).to_string());
+    let result = decode_flattened_json(
+        &flattened(&tampered_payload),
+        &verifying_key,
+        &validation_options,
+    );
+    assert!(matches!(result, Err(Error::InvalidSignature)));
+}